@@ -0,0 +1,46 @@
+//! Compare loading a commitment to the full 128x1024 iris image by decompressing every point
+//! against loading it with `CommitmentHints`, which skip the square root per point.
+use ark_bn254::G1Projective as Bn256Point;
+use hyrax::entropy::test_seed;
+use hyrax::iriscode_commit::{
+    compute_commitments_binary_outputs, deserialize_commitment_from_bytes_compressed,
+    deserialize_commitment_with_hints, generate_hints,
+};
+use std::time::Instant;
+
+const V3_IMAGE_SIZE: usize = 128 * 1024;
+const NUM_RUNS: u32 = 100;
+
+/// Usage: `cargo run --release --bin benchmark_commitment_hints`
+fn main() {
+    let image: Vec<u8> = (0..V3_IMAGE_SIZE).map(|i| (i * 31 % 256) as u8).collect();
+    let commitment_bytes = compute_commitments_binary_outputs(&image, test_seed("hints"))
+        .unwrap()
+        .commitment_serialized;
+    let hints = generate_hints::<Bn256Point>(&commitment_bytes).unwrap();
+
+    let start_time = Instant::now();
+    let mut decompressed = vec![];
+    for _ in 0..NUM_RUNS {
+        decompressed =
+            deserialize_commitment_from_bytes_compressed::<Bn256Point>(&commitment_bytes).unwrap();
+    }
+    let decompression_time = start_time.elapsed() / NUM_RUNS;
+
+    let start_time = Instant::now();
+    let mut hinted = vec![];
+    for _ in 0..NUM_RUNS {
+        hinted = deserialize_commitment_with_hints::<Bn256Point>(&commitment_bytes, Some(&hints))
+            .unwrap();
+    }
+    let hinted_time = start_time.elapsed() / NUM_RUNS;
+
+    assert_eq!(decompressed, hinted);
+    println!("Loading a commitment of {} rows:", decompressed.len());
+    println!("  full decompression: {:?}", decompression_time);
+    println!(
+        "  with hints:         {:?} ({:.2}x)",
+        hinted_time,
+        decompression_time.as_secs_f64() / hinted_time.as_secs_f64()
+    );
+}
@@ -0,0 +1,31 @@
+//! Time building a committer with 512 generators and doublings tables of 32 bits, as for
+//! `integer_vector_commit` on i32 data. Run once as below and once with `--no-default-features`
+//! to compare the parallel doublings precompute of the `rayon` feature with the serial one.
+use ark_bn254::G1Projective as Bn256Point;
+use hyrax::iriscode_commit::{LOG_NUM_COLS, PUBLIC_STRING};
+use hyrax::pedersen::PedersenCommitter;
+use std::time::Instant;
+
+const INT_ABS_VAL_BITWIDTH: usize = 32;
+const NUM_RUNS: u32 = 10;
+
+/// Usage: `cargo run --release [--no-default-features] --bin benchmark_committer_setup`
+fn main() {
+    let start_time = Instant::now();
+    for _ in 0..NUM_RUNS {
+        let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new_with_bitwidth(
+            1 << LOG_NUM_COLS,
+            PUBLIC_STRING,
+            INT_ABS_VAL_BITWIDTH,
+        )
+        .unwrap();
+        assert_eq!(committer.generators.len(), 1 << LOG_NUM_COLS);
+    }
+    let setup_time = start_time.elapsed() / NUM_RUNS;
+
+    println!(
+        "new_with_bitwidth({}, _, {INT_ABS_VAL_BITWIDTH}) with the parallel precompute {}: {setup_time:?}",
+        1 << LOG_NUM_COLS,
+        if cfg!(feature = "rayon") { "on" } else { "off" },
+    );
+}
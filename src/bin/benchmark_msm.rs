@@ -0,0 +1,59 @@
+//! Compare the multi-scalar multiplication implementations against the naive fold,
+//! for a single row (512 generators) of the iris image commitment.
+use ark_bn254::G1Projective as Bn256Point;
+use ark_ff::UniformRand;
+use hyrax::curves::{pippenger_msm, PrimeOrderCurve};
+use hyrax::iriscode_commit::{LOG_NUM_COLS, PUBLIC_STRING};
+use hyrax::pedersen::PedersenCommitter;
+use std::time::{Duration, Instant};
+
+const NUM_RUNS: u32 = 20;
+
+fn time_runs(mut f: impl FnMut() -> Bn256Point) -> (Duration, Bn256Point) {
+    let start_time = Instant::now();
+    let mut result = <Bn256Point as PrimeOrderCurve>::zero();
+    for _ in 0..NUM_RUNS {
+        result = f();
+    }
+    (start_time.elapsed() / NUM_RUNS, result)
+}
+
+/// Usage: `cargo run --release --bin benchmark_msm`
+fn main() {
+    let mut rng = rand::thread_rng();
+    let points: Vec<Bn256Point> = (0..1 << LOG_NUM_COLS)
+        .map(|_| Bn256Point::random(&mut rng))
+        .collect();
+    let scalars: Vec<<Bn256Point as PrimeOrderCurve>::Scalar> = (0..1 << LOG_NUM_COLS)
+        .map(|_| UniformRand::rand(&mut rng))
+        .collect();
+
+    let (naive_time, naive) = time_runs(|| {
+        scalars.iter().zip(points.iter()).fold(
+            <Bn256Point as PrimeOrderCurve>::zero(),
+            |acc, (scalar, point)| acc + *point * *scalar,
+        )
+    });
+    let (pippenger_time, pippenger) = time_runs(|| pippenger_msm(&scalars, &points));
+    let (msm_time, msm) = time_runs(|| <Bn256Point as PrimeOrderCurve>::msm(&scalars, &points));
+    assert_eq!(naive, pippenger);
+    assert_eq!(naive, msm);
+
+    // --- Against the committer's own generators, with fixed-base comb tables ---
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING).precompute_comb(8);
+    let zero = <Bn256Point as PrimeOrderCurve>::Scalar::from(0u64);
+    let (comb_time, comb) = time_runs(|| committer.vector_commit_comb(&scalars, &zero));
+    let (generators_msm_time, generators_msm) =
+        time_runs(|| <Bn256Point as PrimeOrderCurve>::msm(&scalars, &committer.generators));
+    assert_eq!(comb, generators_msm);
+
+    println!("MSM of {} random scalars and points:", points.len());
+    println!("  naive fold:                {:?}", naive_time);
+    println!("  pippenger_msm (default):   {:?}", pippenger_time);
+    println!("  PrimeOrderCurve::msm:      {:?}", msm_time);
+    println!(
+        "  comb tables (8-bit window): {:?} (msm on the same generators: {:?})",
+        comb_time, generators_msm_time
+    );
+}
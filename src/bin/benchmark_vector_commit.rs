@@ -0,0 +1,42 @@
+//! Compare the two row commitment strategies of `PedersenCommitter` on the full
+//! 128x1024 iris image: summing precomputed doublings vs. multi-scalar multiplication.
+use ark_bn254::Fr as Bn256Scalar;
+use ark_bn254::G1Projective as Bn256Point;
+use hyrax::iriscode_commit::{LOG_NUM_COLS, PUBLIC_STRING};
+use hyrax::pedersen::PedersenCommitter;
+use rand::RngCore;
+use std::time::Instant;
+
+const V3_IMAGE_SIZE: usize = 128 * 1024;
+
+/// Usage: `cargo run --release --bin benchmark_vector_commit`
+fn main() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let mut image = vec![0u8; V3_IMAGE_SIZE];
+    rand::thread_rng().fill_bytes(&mut image);
+    let blinding_factor = Bn256Scalar::from(1u64);
+
+    let start_time = Instant::now();
+    let doublings: Vec<Bn256Point> = image
+        .chunks(1 << LOG_NUM_COLS)
+        .map(|row| committer.vector_commit_with_doublings(row, &blinding_factor))
+        .collect();
+    let doublings_time = start_time.elapsed();
+
+    let start_time = Instant::now();
+    let msm: Vec<Bn256Point> = image
+        .chunks(1 << LOG_NUM_COLS)
+        .map(|row| committer.vector_commit_with_msm(row, &blinding_factor))
+        .collect();
+    let msm_time = start_time.elapsed();
+
+    assert_eq!(doublings, msm);
+    println!(
+        "Committing to {} rows of {} bytes (single-threaded):",
+        doublings.len(),
+        1 << LOG_NUM_COLS
+    );
+    println!("  precomputed doublings: {:?}", doublings_time);
+    println!("  multi-scalar mult.:    {:?}", msm_time);
+}
@@ -0,0 +1,53 @@
+//! Compare committing to a row (512 generators) of full-width scalars with the multi-scalar
+//! multiplication of `scalar_vector_commit` against the fixed-base window tables of
+//! `vector_commit_windowed`.
+use ark_bn254::Fr as Bn256Scalar;
+use ark_bn254::G1Projective as Bn256Point;
+use ark_ff::UniformRand;
+use hyrax::iriscode_commit::{LOG_NUM_COLS, PUBLIC_STRING};
+use hyrax::pedersen::PedersenCommitter;
+use std::time::Instant;
+
+const NUM_RUNS: u32 = 20;
+const WINDOW_BITS: usize = 4;
+
+/// Usage: `cargo run --release --bin benchmark_windowed_commit`
+fn main() {
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let message: Vec<Bn256Scalar> = (0..1 << LOG_NUM_COLS)
+        .map(|_| Bn256Scalar::rand(&mut rng))
+        .collect();
+    let blinding_factor = Bn256Scalar::rand(&mut rng);
+
+    let start_time = Instant::now();
+    let table = committer.build_window_table(WINDOW_BITS);
+    let table_time = start_time.elapsed();
+
+    let start_time = Instant::now();
+    let mut msm = Bn256Point::default();
+    for _ in 0..NUM_RUNS {
+        msm = committer.scalar_vector_commit(&message, &blinding_factor);
+    }
+    let msm_time = start_time.elapsed() / NUM_RUNS;
+
+    let start_time = Instant::now();
+    let mut windowed = Bn256Point::default();
+    for _ in 0..NUM_RUNS {
+        windowed = committer.vector_commit_windowed(&message, &blinding_factor, &table);
+    }
+    let windowed_time = start_time.elapsed() / NUM_RUNS;
+
+    assert_eq!(msm, windowed);
+    println!(
+        "Committing to {} full-width scalars (mean of {} runs):",
+        1 << LOG_NUM_COLS,
+        NUM_RUNS
+    );
+    println!("  multi-scalar mult.:      {:?}", msm_time);
+    println!(
+        "  {}-bit window tables:     {:?} (tables built once in {:?})",
+        WINDOW_BITS, windowed_time, table_time
+    );
+}
@@ -0,0 +1,52 @@
+//! Compares two serialized commitments, e.g. to confirm that two independently generated
+//! commitments to the same image (with the same blinding factor seed) match.
+use clap::Parser;
+use hyrax::iriscode_commit::{
+    deserialize_commitment_from_bytes_compressed_concrete, diff_commitments, CommitmentDiff,
+};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// filepath of the first serialized commitment.
+    #[arg(long)]
+    left_commitment_filepath: String,
+
+    /// filepath of the second serialized commitment.
+    #[arg(long)]
+    right_commitment_filepath: String,
+}
+
+/// Usage: `cargo run --release --bin compare_commitments -- --left-commitment-filepath a.bin --right-commitment-filepath b.bin`
+/// Exits with status 1 if the commitments differ, and 2 if either file cannot be read or decoded.
+fn main() {
+    let args = Args::parse();
+    let left = read_commitment(&args.left_commitment_filepath);
+    let right = read_commitment(&args.right_commitment_filepath);
+
+    match diff_commitments(&left, &right) {
+        CommitmentDiff::Equal => println!("commitments are equal ({} rows)", left.len()),
+        CommitmentDiff::DifferentLengths {
+            left_rows,
+            right_rows,
+        } => {
+            println!("commitments differ in length: {left_rows} rows vs. {right_rows} rows");
+            std::process::exit(1);
+        }
+        CommitmentDiff::FirstDifferingRow(row) => {
+            println!("commitments differ, first at row {row}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_commitment(filepath: &str) -> Vec<ark_bn254::G1Projective> {
+    let bytes = std::fs::read(filepath).unwrap_or_else(|err| {
+        eprintln!("could not read {filepath}: {err}");
+        std::process::exit(2);
+    });
+    deserialize_commitment_from_bytes_compressed_concrete(&bytes).unwrap_or_else(|err| {
+        eprintln!("could not decode the commitment in {filepath}: {err}");
+        std::process::exit(2);
+    })
+}
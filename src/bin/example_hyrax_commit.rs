@@ -1,12 +1,11 @@
-/// Measure how long it takes to commit to the Worldcoin iris image.
-/// Random u8 values are used as a stand in for the normalized iris image.
+//! Measure how long it takes to commit to the Worldcoin iris image.
+//! Random u8 values are used as a stand in for the normalized iris image.
+use hyrax::entropy::production_seed;
 use hyrax::iriscode_commit::{compute_commitments_binary_outputs, HyraxCommitmentOutputSerialized};
 use hyrax::utils::{
     read_bytes_from_file, write_bytes_to_file, BLINDING_FACTORS_FILENAME, COMMITMENT_FILENAME,
     INPUT_NORMALIZED_IMAGE_FILENAME,
 };
-use rand::RngCore;
-use rand_core::OsRng;
 use std::time::Instant;
 
 /// Usage: `cargo run --release` from this directory (remainder-hyrax-tfh/hyrax/src/bin)
@@ -16,18 +15,44 @@ fn main() {
 
     let start_time = Instant::now();
 
-    // Sample randomness for the generation of the blinding factors (note that `OsRng` calls `/dev/urandom` under the hood)
+    // Sample randomness for the generation of the blinding factors (note that `production_seed` calls `OsRng`,
+    // i.e. `/dev/urandom`, under the hood and health-checks its output)
     // (You will need to do this with what you determine is a good source of entropy!)
-    let mut seed = [0u8; 32];
-    OsRng.fill_bytes(&mut seed);
+    let seed = production_seed().expect("failed to sample a blinding factor seed");
 
     // The actual commitment function, generating commitments and blinding factors
     let HyraxCommitmentOutputSerialized {
         commitment_serialized,
         blinding_factors_serialized,
-    } = compute_commitments_binary_outputs(&iris_image, seed);
+        ..
+    } = compute_commitments_binary_outputs(&iris_image, seed).unwrap_or_else(|err| {
+        eprintln!("Failed to commit to the iris image: {err}");
+        std::process::exit(1)
+    });
 
-    println!("Computing commitment took: {:?}", start_time.elapsed());
+    let parallel_time = start_time.elapsed();
+    println!("Computing commitment took: {:?}", parallel_time);
+
+    // For comparison, the same commitment with the rows committed to one at a time
+    let start_time = Instant::now();
+    let sequential_output = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(|| compute_commitments_binary_outputs(&iris_image, seed))
+        .unwrap();
+    let sequential_time = start_time.elapsed();
+    assert_eq!(
+        sequential_output.commitment_serialized,
+        commitment_serialized
+    );
+    println!(
+        "Single-threaded: {:?}, parallel ({} threads): {:?} ({:.2}x)",
+        sequential_time,
+        rayon::current_num_threads(),
+        parallel_time,
+        sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
 
     // Sample serialization to file (iris image, blinding factors)
     write_bytes_to_file(COMMITMENT_FILENAME, &commitment_serialized);
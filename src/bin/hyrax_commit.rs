@@ -1,10 +1,12 @@
+//! Measure how long it takes to commit to the Worldcoin iris image.
+//! Random u8 values are used as a stand in for the normalized iris image.
 use clap::Parser;
-/// Measure how long it takes to commit to the Worldcoin iris image.
-/// Random u8 values are used as a stand in for the normalized iris image.
-use hyrax::iriscode_commit::{compute_commitments_binary_outputs, HyraxCommitmentOutputSerialized};
+use hyrax::entropy::{production_seed, BlindingSeed};
+use hyrax::iriscode_commit::{
+    compute_commitments_binary_outputs, regenerate_blinding_factors, BlindingDerivation,
+    HyraxCommitmentOutputSerialized, RngKind,
+};
 use hyrax::utils::{read_bytes_from_file, write_bytes_to_file};
-use rand::RngCore;
-use rand_core::OsRng;
 
 const V2_IMAGE_SIZE: usize = 100 * 400;
 const V3_IMAGE_SIZE: usize = 128 * 1024;
@@ -13,47 +15,107 @@ const V3_IMAGE_SIZE: usize = 128 * 1024;
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// this is the filepath which contains the array of bytes representing the normalized iris image.
-    #[arg(long)]
-    input_image_filepath: String,
+    #[arg(long, required_unless_present = "regenerate_blinding")]
+    input_image_filepath: Option<String>,
 
     /// this is the filepath to which the commitment to the iris image provided will be written.
     /// NOTE: contents can be decryptable by the backend servers and by the phone
-    #[arg(long)]
-    output_commitment_filepath: String,
+    #[arg(long, required_unless_present = "regenerate_blinding")]
+    output_commitment_filepath: Option<String>,
 
     /// this is the filepath to which the blinding factors used to produce the commitment will be written.
     /// NOTE: contents should only be able to be decrypted by the phone
     #[arg(long)]
     output_blinding_factors_filepath: String,
+
+    /// if given, the filepath to which the blinding factor seed is written, so that it can be escrowed.
+    /// NOTE: like the blinding factors, contents should only be able to be decrypted by the phone
+    #[arg(long, conflicts_with = "regenerate_blinding")]
+    output_seed_filepath: Option<String>,
+
+    /// regenerate the blinding factors from an escrowed seed instead of committing to an image.
+    /// Only the blinding factors file is written.
+    #[arg(long, requires_all = ["seed_filepath", "num_rows"])]
+    regenerate_blinding: bool,
+
+    /// this is the filepath which contains the escrowed 32-byte blinding factor seed.
+    #[arg(long)]
+    seed_filepath: Option<String>,
+
+    /// the number of rows of the commitment whose blinding factors are regenerated
+    /// (256 for a V3 image, 128 for a V2 image).
+    #[arg(long)]
+    num_rows: Option<usize>,
+
+    /// how the blinding factors were derived from the escrowed seed: `chacha20` for commitments
+    /// made by this binary (or `compute_commitments`), `chacha12`/`chacha8` for
+    /// `compute_commitments_with_rng_kind`, `per-row` for `compute_commitments_parallel`.
+    #[arg(long, value_enum, default_value_t = Derivation::Chacha20, requires = "regenerate_blinding")]
+    derivation: Derivation,
+}
+
+/// The values of `--derivation`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Derivation {
+    Chacha20,
+    Chacha12,
+    Chacha8,
+    PerRow,
+}
+
+impl From<Derivation> for BlindingDerivation {
+    fn from(derivation: Derivation) -> Self {
+        match derivation {
+            Derivation::Chacha20 => BlindingDerivation::Sequential(RngKind::ChaCha20),
+            Derivation::Chacha12 => BlindingDerivation::Sequential(RngKind::ChaCha12),
+            Derivation::Chacha8 => BlindingDerivation::Sequential(RngKind::ChaCha8),
+            Derivation::PerRow => BlindingDerivation::PerRow,
+        }
+    }
 }
 
 /// Usage: `cargo build --release && cargo run --release --bin hyrax_commit`
 fn main() {
     let args = Args::parse();
+    if args.regenerate_blinding {
+        regenerate_blinding(&args);
+        return;
+    }
+
     // Generate a random image to be committed to; this is a stand-in for the iris image ---
-    let iris_image = read_bytes_from_file(&args.input_image_filepath);
+    let input_image_filepath = args.input_image_filepath.unwrap();
+    let output_commitment_filepath = args.output_commitment_filepath.unwrap();
+    let iris_image = read_bytes_from_file(&input_image_filepath);
     // Sanity check on expected image dimensions
     assert!((iris_image.len() == V2_IMAGE_SIZE) || (iris_image.len() == V3_IMAGE_SIZE));
 
-    // Sample randomness for the generation of the blinding factors (note that `OsRng` calls `/dev/urandom` under the hood)
-    let mut seed = [0u8; 32];
-    OsRng.fill_bytes(&mut seed);
+    // Sample randomness for the generation of the blinding factors (note that `production_seed` calls `OsRng`,
+    // i.e. `/dev/urandom`, under the hood and health-checks its output)
+    let seed = production_seed().expect("failed to sample a blinding factor seed");
 
     // The actual commitment function, generating commitments and blinding factors
     let HyraxCommitmentOutputSerialized {
         commitment_serialized,
         blinding_factors_serialized,
-    } = compute_commitments_binary_outputs(&iris_image, seed);
+        ..
+    } = compute_commitments_binary_outputs(&iris_image, seed).unwrap_or_else(|err| {
+        eprintln!("Failed to commit to the iris image: {err}");
+        std::process::exit(1)
+    });
+
+    if let Some(output_seed_filepath) = &args.output_seed_filepath {
+        write_bytes_to_file(output_seed_filepath, &seed.bytes());
+    }
 
     // Sample serialization to file (iris image, blinding factors)
-    write_bytes_to_file(&args.output_commitment_filepath, &commitment_serialized);
+    write_bytes_to_file(&output_commitment_filepath, &commitment_serialized);
     write_bytes_to_file(
         &args.output_blinding_factors_filepath,
         &blinding_factors_serialized,
     );
 
     // Sample serialization from file (iris image, blinding factors);
-    let commitment_bytes_from_file = read_bytes_from_file(&args.output_commitment_filepath);
+    let commitment_bytes_from_file = read_bytes_from_file(&output_commitment_filepath);
     let blinding_factors_bytes_from_file =
         read_bytes_from_file(&args.output_blinding_factors_filepath);
 
@@ -64,3 +126,19 @@ fn main() {
         blinding_factors_serialized
     );
 }
+
+/// Regenerates the blinding factors from the escrowed seed, without recomputing the commitment.
+fn regenerate_blinding(args: &Args) {
+    let seed_bytes = read_bytes_from_file(args.seed_filepath.as_ref().unwrap());
+    let seed_bytes: [u8; 32] = seed_bytes.try_into().unwrap_or_else(|bytes: Vec<u8>| {
+        eprintln!("Expected a 32-byte seed, got {} bytes", bytes.len());
+        std::process::exit(1)
+    });
+    let seed = BlindingSeed::from(seed_bytes);
+    let blinding_factors_serialized =
+        regenerate_blinding_factors(&seed, args.num_rows.unwrap(), args.derivation.into());
+    write_bytes_to_file(
+        &args.output_blinding_factors_filepath,
+        &blinding_factors_serialized,
+    );
+}
@@ -0,0 +1,48 @@
+//! Soak test for hardware qualification: repeatedly commits to random images and
+//! cross-checks the results, to detect silent arithmetic corruption.
+use ark_bn254::G1Projective as Bn256Point;
+use clap::Parser;
+use hyrax::iriscode_commit::LOG_NUM_COLS;
+use hyrax::soak::{run_soak, SoakConfig};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// number of random images to commit to.
+    #[arg(long, default_value_t = 1000)]
+    iterations: usize,
+
+    /// length in bytes of each random image (defaults to the V3 iris image size).
+    #[arg(long, default_value_t = 128 * 1024)]
+    data_len: usize,
+
+    /// seed for the stream of random images, so that a run can be replayed.
+    #[arg(long, default_value_t = 0)]
+    rng_seed: u64,
+
+    /// re-verify against the naive reference every this many iterations (0 disables it).
+    #[arg(long, default_value_t = 10)]
+    reference_check_interval: usize,
+}
+
+/// Usage: `cargo run --release --bin hyrax_soak -- --iterations 100000 > soak_report.json`
+fn main() {
+    let args = Args::parse();
+    let config = SoakConfig {
+        iterations: args.iterations,
+        data_len: args.data_len,
+        log_num_cols: LOG_NUM_COLS,
+        rng_seed: args.rng_seed,
+        reference_check_interval: args.reference_check_interval,
+    };
+
+    let report = run_soak::<Bn256Point>(&config).unwrap_or_else(|err| {
+        eprintln!("Invalid soak configuration: {err}");
+        std::process::exit(2)
+    });
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+
+    if !report.mismatches.is_empty() {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,38 @@
+//! Samples the Pedersen generators used to commit to the iris image and writes them to file, so
+//! that the Orb can load them with `PedersenCommitter::from_bytes` instead of resampling them.
+use ark_bn254::G1Projective as Bn256Point;
+use clap::Parser;
+use hyrax::iriscode_commit::{LOG_NUM_COLS, PUBLIC_STRING};
+use hyrax::pedersen::PedersenCommitter;
+use hyrax::utils::{read_bytes_from_file, write_bytes_to_file};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// filepath to write the serialized generators to.
+    #[arg(long)]
+    generators_filepath: String,
+
+    /// log2 of the number of message generators, i.e. of the number of columns of the matrix.
+    #[arg(long, default_value_t = LOG_NUM_COLS)]
+    log_num_cols: usize,
+}
+
+/// Usage: `cargo run --release --bin serialize_generators -- --generators-filepath generators.bin`
+fn main() {
+    let args = Args::parse();
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << args.log_num_cols, PUBLIC_STRING);
+    write_bytes_to_file(&args.generators_filepath, &committer.to_bytes());
+
+    // --- Check that the file loads back into the same committer ---
+    let loaded: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::from_bytes(&read_bytes_from_file(&args.generators_filepath))
+            .expect("failed to load the written generators");
+    assert!(loaded.matches_public_string(PUBLIC_STRING));
+    println!(
+        "wrote {} generators (and the blinding generator) to {}",
+        loaded.generators.len(),
+        args.generators_filepath
+    );
+}
@@ -0,0 +1,214 @@
+//! The Grumpkin curve, y^2 = x^3 - 17 over the BN254 scalar field, whose group order is the
+//! BN254 base field modulus. BN254 and Grumpkin thus form a cycle of curves: the scalars of one
+//! are the coordinates of the other, which is what cycle-based recursive proving relies on.
+use super::{canonical_from_le_bytes, infinity_bytes, is_infinity_encoding, PrimeOrderCurve};
+use crate::error::HyraxError;
+use ark_bn254::{Fq as GrumpkinScalar, Fr as GrumpkinBase};
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
+use ark_ec::{CurveConfig, CurveGroup, Group, VariableBaseMSM};
+use ark_ff::{BigInteger, MontFp, PrimeField};
+use itertools::Itertools;
+use num_traits::{One, Zero};
+use rand_core::RngCore;
+
+/// The arkworks parameters of the Grumpkin curve.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrumpkinConfig;
+
+impl CurveConfig for GrumpkinConfig {
+    type BaseField = GrumpkinBase;
+    type ScalarField = GrumpkinScalar;
+
+    /// The curve has prime order.
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: GrumpkinScalar = MontFp!("1");
+}
+
+impl SWCurveConfig for GrumpkinConfig {
+    const COEFF_A: GrumpkinBase = MontFp!("0");
+    const COEFF_B: GrumpkinBase = MontFp!("-17");
+    /// (1, sqrt(-16)), the generator used by Aztec/Noir.
+    const GENERATOR: Affine<Self> = Affine::new_unchecked(
+        MontFp!("1"),
+        MontFp!("17631683881184975370165255887551781615748388533673675138860"),
+    );
+}
+
+pub type GrumpkinAffine = Affine<GrumpkinConfig>;
+pub type Grumpkin = Projective<GrumpkinConfig>;
+
+impl PrimeOrderCurve for Grumpkin {
+    type Scalar = GrumpkinScalar;
+    type Base = GrumpkinBase;
+
+    const UNCOMPRESSED_CURVE_POINT_BYTEWIDTH: usize = 65;
+    const COMPRESSED_CURVE_POINT_BYTEWIDTH: usize = 34;
+    const SCALAR_ELEM_BYTEWIDTH: usize = 32;
+    const CURVE_NAME: &'static str = "Grumpkin";
+
+    fn zero() -> Self {
+        Grumpkin::default()
+    }
+
+    fn a() -> Self::Base {
+        GrumpkinConfig::COEFF_A
+    }
+
+    fn b() -> Self::Base {
+        GrumpkinConfig::COEFF_B
+    }
+
+    fn is_on_curve(&self) -> bool {
+        if self.is_zero() {
+            true
+        } else {
+            let (x, y) = self.affine_coordinates().unwrap();
+            ((x * x + Grumpkin::a()) * x + Grumpkin::b()) == y * y
+        }
+    }
+
+    fn generator() -> Self {
+        GrumpkinConfig::GENERATOR.into()
+    }
+
+    fn random(mut rng: impl RngCore) -> Self {
+        // loop until we have a point where the y-coord has a valid square root
+        loop {
+            let mut random_bytes = [0; 64];
+            rng.fill_bytes(&mut random_bytes[..]);
+            let x_coord = GrumpkinBase::from_le_bytes_mod_order(&random_bytes);
+            let yparity_wanted = (rng.next_u32() % 2) as u8;
+
+            if let Some((y_option_1, y_option_2)) = GrumpkinAffine::get_ys_from_x_unchecked(x_coord)
+            {
+                let y_option_1_parity = y_option_1.into_bigint().to_bytes_le()[0] & 1;
+                let y_coord = if yparity_wanted ^ y_option_1_parity == 0 {
+                    y_option_1
+                } else {
+                    y_option_2
+                };
+                return Self {
+                    x: x_coord,
+                    y: y_coord,
+                    z: Self::Base::one(),
+                };
+            }
+        }
+    }
+
+    fn double(&self) -> Self {
+        Group::double(self)
+    }
+
+    fn projective_coordinates(&self) -> (Self::Base, Self::Base, Self::Base) {
+        if let Some((x, y)) = self.affine_coordinates() {
+            (x, y, Self::Base::one())
+        } else {
+            // it's the identity element
+            (Self::Base::zero(), Self::Base::one(), Self::Base::zero())
+        }
+    }
+
+    fn affine_coordinates(&self) -> Option<(Self::Base, Self::Base)> {
+        if self.is_zero() {
+            None
+        } else {
+            let coord = self.into_affine();
+            Some((coord.x, coord.y))
+        }
+    }
+
+    /// Same layout as for BN256: an infinity flag byte, then the little endian x- and
+    /// y-coordinates.
+    fn to_bytes_uncompressed(&self) -> Vec<u8> {
+        if let Some((x, y)) = self.affine_coordinates() {
+            let all_bytes = std::iter::once(0_u8)
+                .chain(x.into_bigint().to_bytes_le())
+                .chain(y.into_bigint().to_bytes_le())
+                .collect_vec();
+            assert_eq!(all_bytes.len(), Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH);
+            all_bytes
+        } else {
+            // --- Point at infinity ---
+            infinity_bytes(Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH)
+        }
+    }
+
+    /// Same layout as for BN256: an infinity flag byte, the little endian x-coordinate, then
+    /// the parity of the y-coordinate.
+    fn to_bytes_compressed(&self) -> Vec<u8> {
+        if let Some((x, y)) = self.affine_coordinates() {
+            let y_parity = y.into_bigint().to_bytes_le()[0] & 1;
+            let all_bytes = std::iter::once(0_u8)
+                .chain(x.into_bigint().to_bytes_le())
+                .chain(std::iter::once(y_parity))
+                .collect_vec();
+            assert_eq!(all_bytes.len(), Self::COMPRESSED_CURVE_POINT_BYTEWIDTH);
+            all_bytes
+        } else {
+            // --- Point at infinity ---
+            infinity_bytes(Self::COMPRESSED_CURVE_POINT_BYTEWIDTH)
+        }
+    }
+
+    fn try_from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, HyraxError> {
+        if bytes.len() != Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH {
+            return Err(HyraxError::InvalidByteLength {
+                expected: Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: bytes.len(),
+            });
+        }
+        if is_infinity_encoding(bytes)? {
+            return Ok(<Self as PrimeOrderCurve>::zero());
+        }
+        let point = Self {
+            x: canonical_from_le_bytes::<Self::Base>(&bytes[1..33])?,
+            y: canonical_from_le_bytes::<Self::Base>(&bytes[33..])?,
+            z: Self::Base::one(),
+        };
+        if !point.is_on_curve() {
+            return Err(HyraxError::PointNotOnCurve);
+        }
+        Ok(point)
+    }
+
+    fn try_from_bytes_compressed(bytes: &[u8]) -> Result<Self, HyraxError> {
+        if bytes.len() != Self::COMPRESSED_CURVE_POINT_BYTEWIDTH {
+            return Err(HyraxError::InvalidByteLength {
+                expected: Self::COMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: bytes.len(),
+            });
+        }
+        if is_infinity_encoding(bytes)? {
+            return Ok(<Self as PrimeOrderCurve>::zero());
+        }
+        let y_sign_byte: u8 = bytes[33];
+        if y_sign_byte > 1 {
+            return Err(HyraxError::DeserializationError(format!(
+                "invalid y-parity byte {y_sign_byte}"
+            )));
+        }
+
+        let x_coord = canonical_from_le_bytes::<Self::Base>(&bytes[1..33])?;
+        let (y_option_1, y_option_2) = GrumpkinAffine::get_ys_from_x_unchecked(x_coord)
+            .ok_or(HyraxError::XCoordinateNotOnCurve)?;
+        let y_coord = if (y_option_1.into_bigint().to_bytes_le()[0] % 2) ^ y_sign_byte == 0 {
+            y_option_1
+        } else {
+            y_option_2
+        };
+
+        Ok(Self {
+            x: x_coord,
+            y: y_coord,
+            z: Self::Base::one(),
+        })
+    }
+
+    /// Uses the arkworks variable-base MSM.
+    fn msm(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        assert_eq!(scalars.len(), points.len());
+        let affine_points = Self::normalize_batch(points);
+        <Self as VariableBaseMSM>::msm_unchecked(&affine_points, scalars)
+    }
+}
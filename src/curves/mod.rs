@@ -1,6 +1,8 @@
+use crate::error::HyraxError;
 use ark_bn254::G1Affine as Bn256;
 use ark_bn254::G1Projective as Bn256Point;
 use ark_bn254::{Fq as Bn256Base, Fr as Bn256Scalar};
+use ark_ec::short_weierstrass::Projective;
 use ark_ec::AffineRepr;
 use ark_ff::BigInteger;
 use ark_ff::{Field, PrimeField};
@@ -9,9 +11,12 @@ use rand_core::RngCore;
 // use serde::{Deserialize, Serialize};
 use ark_ec::CurveGroup;
 use ark_ec::Group;
+use ark_ec::VariableBaseMSM;
 use num_traits::One;
 use num_traits::Zero;
 
+pub mod grumpkin;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -51,6 +56,9 @@ pub trait PrimeOrderCurve:
     const COMPRESSED_CURVE_POINT_BYTEWIDTH: usize;
     const SCALAR_ELEM_BYTEWIDTH: usize;
 
+    /// A short, stable name identifying the curve (e.g. in setup manifests).
+    const CURVE_NAME: &'static str;
+
     /// Return the additive identity of the curve.
     fn zero() -> Self;
 
@@ -85,19 +93,252 @@ pub trait PrimeOrderCurve:
     fn to_bytes_compressed(&self) -> Vec<u8>;
 
     /// Returns the unique curve element represented by the uncompressed bytestring.
-    fn from_bytes_uncompressed(bytes: &[u8]) -> Self;
+    /// Panics if the bytes are malformed; see `try_from_bytes_uncompressed`.
+    fn from_bytes_uncompressed(bytes: &[u8]) -> Self {
+        Self::try_from_bytes_uncompressed(bytes).expect("malformed uncompressed curve point")
+    }
 
     /// Returns the unique curve element represented by the compressed bytestring.
-    fn from_bytes_compressed(bytes: &[u8]) -> Self;
+    /// Panics if the bytes are malformed; see `try_from_bytes_compressed`.
+    fn from_bytes_compressed(bytes: &[u8]) -> Self {
+        Self::try_from_bytes_compressed(bytes).expect("malformed compressed curve point")
+    }
+
+    /// Returns the unique curve element represented by the uncompressed bytestring, or an
+    /// error if the bytes have the wrong length, a coordinate is not reduced modulo the field
+    /// order, or the coordinates do not describe a point on the curve.
+    fn try_from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, HyraxError>;
+
+    /// Returns the unique curve element represented by the compressed bytestring, or an
+    /// error if the bytes have the wrong length, the x-coordinate is not reduced modulo the
+    /// field order or is not that of a point on the curve, or the parity byte is invalid.
+    fn try_from_bytes_compressed(bytes: &[u8]) -> Result<Self, HyraxError>;
+
+    /// Multi-scalar multiplication: returns sum_i scalars[i] * points[i].
+    /// Defaults to `pippenger_msm`; implementations may override this with a
+    /// library-optimized MSM.
+    /// Pre: scalars.len() == points.len()
+    fn msm(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        pippenger_msm(scalars, points)
+    }
+}
+
+/// Decodes a little endian field element, rejecting encodings of values at least the field order
+/// (which `from_le_bytes_mod_order` would silently reduce), so each element has one encoding.
+pub(crate) fn canonical_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, HyraxError> {
+    let element = F::from_le_bytes_mod_order(bytes);
+    if element.into_bigint().to_bytes_le() != bytes {
+        return Err(HyraxError::NonCanonicalCoordinate);
+    }
+    Ok(element)
+}
+
+/// Returns the canonical serialization of the point at infinity: the flag byte 1 followed by
+/// `bytewidth - 1` zero bytes.
+pub(crate) fn infinity_bytes(bytewidth: usize) -> Vec<u8> {
+    let mut bytes = vec![0_u8; bytewidth];
+    bytes[0] = 1;
+    bytes
+}
+
+/// Checks the flag byte of a serialized point and returns whether it is the point at infinity.
+/// The flag must be 0 or 1, and the point at infinity must be exactly `infinity_bytes`, so that
+/// every point has a single byte representation.
+pub(crate) fn is_infinity_encoding(bytes: &[u8]) -> Result<bool, HyraxError> {
+    match bytes[0] {
+        0 => Ok(false),
+        1 if bytes[1..].iter().all(|byte| *byte == 0) => Ok(true),
+        1 => Err(HyraxError::NonCanonicalInfinity),
+        flag => Err(HyraxError::InvalidPointFlag(flag)),
+    }
+}
+
+/// Multi-scalar multiplication via Pippenger's bucket method.
+/// Only the windows up to the bit length of the largest scalar are processed, so
+/// MSMs over small scalars (e.g. u8 message elements) are correspondingly cheap.
+/// Pre: scalars.len() == points.len()
+pub fn pippenger_msm<C: PrimeOrderCurve>(scalars: &[C::Scalar], points: &[C]) -> C {
+    assert_eq!(scalars.len(), points.len());
+    let scalar_bits = scalars
+        .iter()
+        .map(|scalar| scalar.into_bigint().to_bits_le())
+        .collect_vec();
+    let num_bits = scalar_bits
+        .iter()
+        .map(|bits| bits.iter().rposition(|bit| *bit).map_or(0, |pos| pos + 1))
+        .max()
+        .unwrap_or(0);
+    if num_bits == 0 {
+        return C::zero();
+    }
+
+    // --- Window size heuristic, as in arkworks: ~ln(n) + 2 bits ---
+    let window_bits = if points.len() < 32 {
+        3
+    } else {
+        (points.len() as f64).ln().ceil() as usize + 2
+    };
+
+    // --- Sum of each window's bucket-weighted points, lowest window first ---
+    let window_sums = (0..num_bits).step_by(window_bits).map(|window_start| {
+        let mut buckets = vec![C::zero(); (1 << window_bits) - 1];
+        scalar_bits
+            .iter()
+            .zip(points.iter())
+            .for_each(|(bits, point)| {
+                let digit = (0..window_bits)
+                    .filter(|offset| bits.get(window_start + offset) == Some(&true))
+                    .fold(0_usize, |acc, offset| acc | (1 << offset));
+                if digit != 0 {
+                    buckets[digit - 1] += *point;
+                }
+            });
+        // sum_j j * buckets[j - 1], via running sums from the top bucket down
+        let mut running_sum = C::zero();
+        let mut window_sum = C::zero();
+        buckets.into_iter().rev().for_each(|bucket| {
+            running_sum += bucket;
+            window_sum += running_sum;
+        });
+        window_sum
+    });
+
+    // --- Combine the windows from the highest down, doubling in between ---
+    window_sums
+        .collect_vec()
+        .into_iter()
+        .rev()
+        .fold(C::zero(), |acc, window_sum| {
+            (0..window_bits).fold(acc, |acc, _| acc.double()) + window_sum
+        })
+}
+
+// The uncompressed encoding of a BN254 point, see `to_bytes_uncompressed`.
+fn bn256_bytes_uncompressed(point: &Bn256Point) -> Vec<u8> {
+    // --- First get the affine coordinates. If `None`, we have a point at infinity. ---
+    let affine_coords = point.affine_coordinates();
+
+    if let Some((x, y)) = affine_coords {
+        let x_bytes = x.into_bigint().to_bytes_le();
+        let y_bytes = y.into_bigint().to_bytes_le();
+        let all_bytes = std::iter::once(0_u8)
+            .chain(x_bytes)
+            .chain(y_bytes)
+            .collect_vec();
+        assert_eq!(
+            all_bytes.len(),
+            <Bn256Point as PrimeOrderCurve>::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH
+        );
+        all_bytes
+    } else {
+        // --- Point at infinity ---
+        infinity_bytes(<Bn256Point as PrimeOrderCurve>::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH)
+    }
+}
+
+// The compressed encoding of a BN254 point, see `to_bytes_compressed`.
+fn bn256_bytes_compressed(point: &Bn256Point) -> Vec<u8> {
+    // --- First get the affine coordinates. If `None`, we have a point at infinity. ---
+    let affine_coords = point.affine_coordinates();
+
+    if let Some((x, y)) = affine_coords {
+        let x_bytes = x.into_bigint().to_bytes_le();
+        // 0 when the square root is even, 1 when the square root is odd. we grab
+        // the parity from the most significant byte and taking the & with 1. the
+        // two square roots of y in the field always have opposite parity because
+        // the field modulus is odd.
+        let y_parity = y.into_bigint().to_bytes_le()[0] & 1;
+        let all_bytes = std::iter::once(0_u8)
+            .chain(x_bytes)
+            .chain(std::iter::once(y_parity))
+            .collect_vec();
+        assert_eq!(
+            all_bytes.len(),
+            <Bn256Point as PrimeOrderCurve>::COMPRESSED_CURVE_POINT_BYTEWIDTH
+        );
+        all_bytes
+    } else {
+        // --- Point at infinity ---
+        infinity_bytes(<Bn256Point as PrimeOrderCurve>::COMPRESSED_CURVE_POINT_BYTEWIDTH)
+    }
+}
+
+/// Asserts (in debug builds) that `point` round-trips through both of its encodings, i.e. that the
+/// compressed and uncompressed encoders agree. Called by both BN254 encoders under the `paranoid`
+/// feature, with the raw encoders so that the check does not recurse.
+#[cfg(feature = "paranoid")]
+pub fn debug_assert_serialization_consistency(point: &Bn256Point) {
+    debug_assert!(
+        encodings_round_trip(
+            point,
+            &bn256_bytes_compressed(point),
+            &bn256_bytes_uncompressed(point)
+        ),
+        "the compressed and uncompressed encodings of {point:?} disagree"
+    );
 }
 
-impl PrimeOrderCurve for Bn256Point {
+/// Checks that each point decodes back to itself from both its compressed and its uncompressed
+/// encoding, e.g. for audit tooling to run over stored commitments, so that the two encoders
+/// cannot silently disagree (e.g. about the y-parity of some class of points).
+/// Errors with `HyraxError::InconsistentPointEncoding` with the index of the first point that
+/// does not round-trip, including points whose encodings fail to decode.
+pub fn check_encoding_consistency<C: PrimeOrderCurve>(points: &[C]) -> Result<(), HyraxError> {
+    match points.iter().position(|point| {
+        !encodings_round_trip(
+            point,
+            &point.to_bytes_compressed(),
+            &point.to_bytes_uncompressed(),
+        )
+    }) {
+        Some(index) => Err(HyraxError::InconsistentPointEncoding(index)),
+        None => Ok(()),
+    }
+}
+
+// Whether both encodings decode to `point`.
+fn encodings_round_trip<C: PrimeOrderCurve>(
+    point: &C,
+    compressed: &[u8],
+    uncompressed: &[u8],
+) -> bool {
+    C::try_from_bytes_compressed(compressed).is_ok_and(|decoded| decoded == *point)
+        && C::try_from_bytes_uncompressed(uncompressed).is_ok_and(|decoded| decoded == *point)
+}
+
+/// Same as `point.to_bytes_compressed()`, but checks that the bytes decode back to a point equal
+/// to `point` (group equality, so that any Jacobian representative of the point passes) before
+/// returning them. Normalizing a representative that is not on the curve, e.g. one produced by an
+/// arithmetic bug, yields the encoding of some other point or of none, which this catches.
+/// Errors with `HyraxError::EncodingRoundTripMismatch` if the bytes do not decode to `point`.
+pub fn encode_checked_compressed<C: PrimeOrderCurve>(point: &C) -> Result<Vec<u8>, HyraxError> {
+    let bytes = point.to_bytes_compressed();
+    match C::try_from_bytes_compressed(&bytes) {
+        Ok(decoded) if decoded == *point => Ok(bytes),
+        _ => Err(HyraxError::EncodingRoundTripMismatch),
+    }
+}
+
+/// Same as `encode_checked_compressed`, for `point.to_bytes_uncompressed()`.
+pub fn encode_checked_uncompressed<C: PrimeOrderCurve>(point: &C) -> Result<Vec<u8>, HyraxError> {
+    let bytes = point.to_bytes_uncompressed();
+    match C::try_from_bytes_uncompressed(&bytes) {
+        Ok(decoded) if decoded == *point => Ok(bytes),
+        _ => Err(HyraxError::EncodingRoundTripMismatch),
+    }
+}
+
+// Implemented for the normalized `Projective<g1::Config>` rather than the `Bn256Point` alias,
+// which goes through an associated type of `BnConfig` that coherence cannot see through, and
+// would otherwise overlap with the Grumpkin implementation.
+impl PrimeOrderCurve for Projective<ark_bn254::g1::Config> {
     type Scalar = Bn256Scalar;
     type Base = Bn256Base;
 
     const UNCOMPRESSED_CURVE_POINT_BYTEWIDTH: usize = 65;
     const COMPRESSED_CURVE_POINT_BYTEWIDTH: usize = 34;
     const SCALAR_ELEM_BYTEWIDTH: usize = 32;
+    const CURVE_NAME: &'static str = "BN254";
 
     fn zero() -> Self {
         Bn256Point::default()
@@ -116,7 +357,7 @@ impl PrimeOrderCurve for Bn256Point {
             true
         } else {
             let (x, y) = self.affine_coordinates().unwrap();
-            ((x * x + Bn256Point::a()) * x + Bn256Point::b()) == y
+            ((x * x + Bn256Point::a()) * x + Bn256Point::b()) == y * y
         }
     }
 
@@ -188,23 +429,13 @@ impl PrimeOrderCurve for Bn256Point {
     ///     infinity (in affine coordinates). 1 if it is at infinity, 0 otherwise.
     /// * The next 32 `u8` bytes represent the x-coordinate of the point in little endian.
     /// * The next 32 `u8` bytes represent the y-coordinate of the point in little endian.
+    ///
+    /// The point at infinity is the flag byte followed by 64 zero bytes.
     fn to_bytes_uncompressed(&self) -> Vec<u8> {
-        // --- First get the affine coordinates. If `None`, we have a point at infinity. ---
-        let affine_coords = self.affine_coordinates();
-
-        if let Some((x, y)) = affine_coords {
-            let x_bytes = x.into_bigint().to_bytes_le();
-            let y_bytes = y.into_bigint().to_bytes_le();
-            let all_bytes = std::iter::once(0_u8)
-                .chain(x_bytes)
-                .chain(y_bytes)
-                .collect_vec();
-            assert_eq!(all_bytes.len(), Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH);
-            all_bytes
-        } else {
-            // --- Point at infinity ---
-            [1_u8; 65].to_vec()
-        }
+        let bytes = bn256_bytes_uncompressed(self);
+        #[cfg(feature = "paranoid")]
+        debug_assert_serialization_consistency(self);
+        bytes
     }
 
     /// The bytestring representation of the BN256 curve is a `[u8; 34]` with
@@ -214,84 +445,80 @@ impl PrimeOrderCurve for Bn256Point {
     /// * The next 32 `u8` bytes represent the x-coordinate of the point in little endian.
     /// * The final `u8` byte represents the sign of the y-coordinate of the
     ///     point.
+    ///
+    /// The point at infinity is the flag byte followed by 33 zero bytes.
     fn to_bytes_compressed(&self) -> Vec<u8> {
-        // --- First get the affine coordinates. If `None`, we have a point at infinity. ---
-        let affine_coords = self.affine_coordinates();
-
-        if let Some((x, y)) = affine_coords {
-            let x_bytes = x.into_bigint().to_bytes_le();
-            // 0 when the square root is even, 1 when the square root is odd. we grab
-            // the parity from the most significant byte and taking the & with 1. the
-            // two square roots of y in the field always have opposite parity because
-            // the field modulus is odd.
-            let y_parity = y.into_bigint().to_bytes_le()[0] & 1;
-            let all_bytes = std::iter::once(0_u8)
-                .chain(x_bytes)
-                .chain(std::iter::once(y_parity))
-                .collect_vec();
-            assert_eq!(all_bytes.len(), Self::COMPRESSED_CURVE_POINT_BYTEWIDTH);
-            all_bytes
-        } else {
-            // --- Point at infinity ---
-            [1_u8; 34].to_vec()
-        }
+        let bytes = bn256_bytes_compressed(self);
+        #[cfg(feature = "paranoid")]
+        debug_assert_serialization_consistency(self);
+        bytes
     }
 
     /// will return the elliptic curve point corresponding to an array of bytes that represent an uncompressed point.
     /// we represent it as a a normalized projective curve point (ie, the x and y coordinates are directly the affine coordinates)
     /// so the z coordinate is always 1.
-    fn from_bytes_uncompressed(bytes: &[u8]) -> Self {
-        // assert that this is a 65 byte representation since it's uncompressed
-        assert_eq!(bytes.len(), Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH);
+    fn try_from_bytes_uncompressed(bytes: &[u8]) -> Result<Self, HyraxError> {
+        // check that this is a 65 byte representation since it's uncompressed
+        if bytes.len() != Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH {
+            return Err(HyraxError::InvalidByteLength {
+                expected: Self::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: bytes.len(),
+            });
+        }
         // first check if it is a point at infinity
-        if bytes[0] == 1_u8 {
-            Self {
+        if is_infinity_encoding(bytes)? {
+            Ok(Self {
                 x: Self::Base::zero(),
                 y: Self::Base::one(),
                 z: Self::Base::zero(),
-            }
+            })
         } else {
-            let mut x_bytes_alloc = [0_u8; 32];
-            let x_bytes = &bytes[1..33];
-            x_bytes_alloc.copy_from_slice(x_bytes);
-
-            let mut y_bytes_alloc = [0_u8; 32];
-            let y_bytes = &bytes[33..];
-            y_bytes_alloc.copy_from_slice(y_bytes);
-
-            let x_coord = Self::Base::from_le_bytes_mod_order(&x_bytes_alloc);
-            let y_coord = Self::Base::from_le_bytes_mod_order(&y_bytes_alloc);
+            let x_coord = canonical_from_le_bytes::<Self::Base>(&bytes[1..33])?;
+            let y_coord = canonical_from_le_bytes::<Self::Base>(&bytes[33..])?;
             let point = Self {
                 x: x_coord,
                 y: y_coord,
                 z: Self::Base::one(),
             };
 
-            assert!(point.is_on_curve());
+            if !point.is_on_curve() {
+                return Err(HyraxError::PointNotOnCurve);
+            }
 
-            point
+            Ok(point)
         }
     }
 
     /// will return the elliptic curve point corresponding to an array of bytes that represent a compressed point.
     /// we represent it as a a normalized projective curve point (ie, the x and y coordinates are directly the affine coordinates)
     /// so the z coordinate is always 1.
-    fn from_bytes_compressed(bytes: &[u8]) -> Self {
-        // assert that this is a 34 byte representation since it's compressed
-        assert_eq!(bytes.len(), Self::COMPRESSED_CURVE_POINT_BYTEWIDTH);
+    fn try_from_bytes_compressed(bytes: &[u8]) -> Result<Self, HyraxError> {
+        // check that this is a 34 byte representation since it's compressed
+        if bytes.len() != Self::COMPRESSED_CURVE_POINT_BYTEWIDTH {
+            return Err(HyraxError::InvalidByteLength {
+                expected: Self::COMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: bytes.len(),
+            });
+        }
         // first check if it is a point at infinity
-        if bytes[0] == 1_u8 {
-            Self {
+        if is_infinity_encoding(bytes)? {
+            Ok(Self {
                 x: Self::Base::zero(),
                 y: Self::Base::one(),
                 z: Self::Base::zero(),
-            }
+            })
         } else {
             let y_sign_byte: u8 = bytes[33];
+            if y_sign_byte > 1 {
+                return Err(HyraxError::DeserializationError(format!(
+                    "invalid y-parity byte {y_sign_byte}"
+                )));
+            }
 
             // y^2 = x^3 + ax + b
-            let x_coord = Self::Base::from_le_bytes_mod_order(&bytes[1..33]);
-            let (y_option_1, y_option_2) = Bn256::get_ys_from_x_unchecked(x_coord).unwrap();
+            let x_coord = canonical_from_le_bytes::<Self::Base>(&bytes[1..33])?;
+            let (y_option_1, y_option_2) =
+                Bn256::get_ys_from_x_unchecked(x_coord).ok_or(HyraxError::XCoordinateNotOnCurve)?;
 
             // --- Flip y-sign if needed ---
             let y_coord = if (y_option_1.into_bigint().to_bytes_le()[0] % 2) ^ y_sign_byte == 0 {
@@ -300,11 +527,18 @@ impl PrimeOrderCurve for Bn256Point {
                 y_option_2
             };
 
-            Self {
+            Ok(Self {
                 x: x_coord,
                 y: y_coord,
                 z: Self::Base::one(),
-            }
+            })
         }
     }
+
+    /// Uses the arkworks variable-base MSM.
+    fn msm(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        assert_eq!(scalars.len(), points.len());
+        let affine_points = Self::normalize_batch(points);
+        <Self as VariableBaseMSM>::msm_unchecked(&affine_points, scalars)
+    }
 }
@@ -1,5 +1,7 @@
+use super::grumpkin::Grumpkin;
 use super::*;
 use ark_bn254::G1Projective as Bn256;
+use ark_ff::UniformRand;
 
 fn test_curve_ops<C: PrimeOrderCurve>() {
     let zero = C::zero();
@@ -24,8 +26,7 @@ fn test_curve_ops<C: PrimeOrderCurve>() {
     assert_eq!(None, zero.affine_coordinates());
     // .. of the generator
     let (x, y) = g.affine_coordinates().unwrap(); // should not panic (since generator is not the identity!)
-    assert_eq!(x, C::Base::from(1u64));
-    assert_eq!(y, C::Base::from(2u64));
+    assert_eq!(y * y, (x * x + C::a()) * x + C::b());
 
     // check the projective coordinates
     // .. of the identity
@@ -61,4 +62,430 @@ fn test_curve_ops<C: PrimeOrderCurve>() {
 #[test]
 fn test_bn256_implementation() {
     test_curve_ops::<Bn256>();
+    let (x, y) = <Bn256 as PrimeOrderCurve>::generator()
+        .affine_coordinates()
+        .unwrap();
+    assert_eq!(x, <Bn256 as PrimeOrderCurve>::Base::from(1u64));
+    assert_eq!(y, <Bn256 as PrimeOrderCurve>::Base::from(2u64));
+}
+
+#[test]
+fn test_grumpkin_implementation() {
+    test_curve_ops::<Grumpkin>();
+}
+
+#[test]
+fn test_grumpkin_generator_and_serialization() {
+    let mut rng = rand::thread_rng();
+    let g = <Grumpkin as PrimeOrderCurve>::generator();
+    assert!(g.is_on_curve());
+    // --- The group order is the BN254 base field modulus ---
+    assert_eq!(g * -<Grumpkin as PrimeOrderCurve>::Scalar::from(1u64), -g);
+    assert_eq!(
+        g.mul_bigint(<Grumpkin as PrimeOrderCurve>::Scalar::MODULUS),
+        <Grumpkin as PrimeOrderCurve>::zero()
+    );
+
+    let points = [g, <Grumpkin as PrimeOrderCurve>::zero()]
+        .into_iter()
+        .chain((0..5).map(|_| Grumpkin::random(&mut rng)));
+    for point in points {
+        assert!(point.is_on_curve());
+        assert_eq!(
+            Grumpkin::from_bytes_compressed(&point.to_bytes_compressed()),
+            point
+        );
+        assert_eq!(
+            Grumpkin::from_bytes_uncompressed(&point.to_bytes_uncompressed()),
+            point
+        );
+    }
+}
+
+#[test]
+fn test_uncompressed_round_trip() {
+    let mut rng = rand::thread_rng();
+    let g = <Bn256 as PrimeOrderCurve>::generator();
+    assert!(g.is_on_curve());
+    assert!(<Bn256 as PrimeOrderCurve>::zero().is_on_curve());
+
+    let points = [g, g + g]
+        .into_iter()
+        .chain((0..5).map(|_| Bn256::random(&mut rng)))
+        .chain([<Bn256 as PrimeOrderCurve>::zero()]);
+    for point in points {
+        let bytes = point.to_bytes_uncompressed();
+        assert_eq!(Bn256::from_bytes_uncompressed(&bytes), point);
+    }
+}
+
+/// Returns the compressed encoding of a (non-identity) x-coordinate for which y^2 = x^3 + 3
+/// has no solution.
+fn compressed_bytes_with_no_point() -> Vec<u8> {
+    let x = (0u64..)
+        .map(<Bn256 as PrimeOrderCurve>::Base::from)
+        .find(|x| ark_bn254::G1Affine::get_ys_from_x_unchecked(*x).is_none())
+        .unwrap();
+    std::iter::once(0_u8)
+        .chain(x.into_bigint().to_bytes_le())
+        .chain(std::iter::once(0_u8))
+        .collect()
+}
+
+#[test]
+fn test_try_from_bytes_rejects_invalid_lengths() {
+    let g = <Bn256 as PrimeOrderCurve>::generator();
+    let compressed = g.to_bytes_compressed();
+    let uncompressed = g.to_bytes_uncompressed();
+    assert_eq!(Bn256::try_from_bytes_compressed(&compressed), Ok(g));
+    assert_eq!(Bn256::try_from_bytes_uncompressed(&uncompressed), Ok(g));
+
+    for len in [0, 33, 35, 65] {
+        assert_eq!(
+            Bn256::try_from_bytes_compressed(&vec![0_u8; len]),
+            Err(HyraxError::InvalidByteLength {
+                expected: Bn256::COMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: len
+            })
+        );
+    }
+    for len in [0, 34, 64, 66] {
+        assert_eq!(
+            Bn256::try_from_bytes_uncompressed(&vec![0_u8; len]),
+            Err(HyraxError::InvalidByteLength {
+                expected: Bn256::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH,
+                got: len
+            })
+        );
+    }
+}
+
+#[test]
+fn test_try_from_bytes_rejects_points_off_the_curve() {
+    // --- Uncompressed: the generator (1, 2) with y replaced by 3 ---
+    let mut uncompressed = <Bn256 as PrimeOrderCurve>::generator().to_bytes_uncompressed();
+    uncompressed[33] = 3;
+    assert_eq!(
+        Bn256::try_from_bytes_uncompressed(&uncompressed),
+        Err(HyraxError::PointNotOnCurve)
+    );
+
+    // --- Compressed: an x-coordinate with no y ---
+    assert_eq!(
+        Bn256::try_from_bytes_compressed(&compressed_bytes_with_no_point()),
+        Err(HyraxError::XCoordinateNotOnCurve)
+    );
+}
+
+#[test]
+fn test_try_from_bytes_rejects_non_canonical_coordinates() {
+    use ark_ff::BigInteger;
+
+    // --- The generator's x = 1 (and y = 2) encoded as x + p (y + p) ---
+    let modulus = <Bn256 as PrimeOrderCurve>::Base::MODULUS;
+    let plus_modulus = |value: u64| {
+        let mut shifted = modulus;
+        shifted.add_with_carry(&value.into());
+        shifted.to_bytes_le()
+    };
+    let g = <Bn256 as PrimeOrderCurve>::generator();
+
+    let mut compressed = g.to_bytes_compressed();
+    compressed[1..33].copy_from_slice(&plus_modulus(1));
+    assert_eq!(
+        Bn256::try_from_bytes_compressed(&compressed),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+
+    let mut uncompressed = g.to_bytes_uncompressed();
+    uncompressed[1..33].copy_from_slice(&plus_modulus(1));
+    assert_eq!(
+        Bn256::try_from_bytes_uncompressed(&uncompressed),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+    let mut uncompressed = g.to_bytes_uncompressed();
+    uncompressed[33..].copy_from_slice(&plus_modulus(2));
+    assert_eq!(
+        Bn256::try_from_bytes_uncompressed(&uncompressed),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+
+    // --- x = p - 1 is the largest canonical x-coordinate ---
+    let mut max_x = g.to_bytes_compressed();
+    max_x[1..33].copy_from_slice(
+        &(-<Bn256 as PrimeOrderCurve>::Base::from(1u64))
+            .into_bigint()
+            .to_bytes_le(),
+    );
+    assert_ne!(
+        Bn256::try_from_bytes_compressed(&max_x),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+}
+
+#[test]
+fn test_try_from_bytes_rejects_truncated_buffers() {
+    let g = <Bn256 as PrimeOrderCurve>::generator();
+    let compressed = g.to_bytes_compressed();
+    let uncompressed = g.to_bytes_uncompressed();
+    for len in 0..compressed.len() {
+        assert!(matches!(
+            Bn256::try_from_bytes_compressed(&compressed[..len]),
+            Err(HyraxError::InvalidByteLength { .. })
+        ));
+    }
+    for len in 0..uncompressed.len() {
+        assert!(matches!(
+            Bn256::try_from_bytes_uncompressed(&uncompressed[..len]),
+            Err(HyraxError::InvalidByteLength { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_try_from_bytes_compressed_rejects_invalid_parity_byte() {
+    let mut compressed = <Bn256 as PrimeOrderCurve>::generator().to_bytes_compressed();
+    compressed[33] = 2;
+    assert!(matches!(
+        Bn256::try_from_bytes_compressed(&compressed),
+        Err(HyraxError::DeserializationError(_))
+    ));
+}
+
+fn test_strict_infinity_encoding<C: PrimeOrderCurve>() {
+    let infinity = <C as PrimeOrderCurve>::zero();
+    let g = <C as PrimeOrderCurve>::generator();
+    for (encoded, decode) in [
+        (
+            infinity.to_bytes_compressed(),
+            C::try_from_bytes_compressed as fn(&[u8]) -> Result<C, HyraxError>,
+        ),
+        (
+            infinity.to_bytes_uncompressed(),
+            C::try_from_bytes_uncompressed,
+        ),
+    ] {
+        // --- The only encoding of infinity is the flag byte followed by zeros ---
+        assert_eq!(encoded[0], 1);
+        assert!(encoded[1..].iter().all(|byte| *byte == 0));
+        assert_eq!(decode(&encoded), Ok(infinity));
+
+        // --- Garbage after the infinity flag ---
+        for idx in 1..encoded.len() {
+            let mut garbage = encoded.clone();
+            garbage[idx] = 0x80;
+            assert_eq!(decode(&garbage), Err(HyraxError::NonCanonicalInfinity));
+        }
+        assert_eq!(
+            decode(&vec![1_u8; encoded.len()]),
+            Err(HyraxError::NonCanonicalInfinity)
+        );
+
+        // --- Corrupted flag bytes, with and without a valid point after them ---
+        let mut point = if encoded.len() == C::COMPRESSED_CURVE_POINT_BYTEWIDTH {
+            g.to_bytes_compressed()
+        } else {
+            g.to_bytes_uncompressed()
+        };
+        for flag in [2_u8, 0x80, 0xff] {
+            let mut corrupted = encoded.clone();
+            corrupted[0] = flag;
+            assert_eq!(decode(&corrupted), Err(HyraxError::InvalidPointFlag(flag)));
+            point[0] = flag;
+            assert_eq!(decode(&point), Err(HyraxError::InvalidPointFlag(flag)));
+        }
+        // --- A point with the infinity flag set is not silently read as infinity ---
+        point[0] = 1;
+        assert_eq!(decode(&point), Err(HyraxError::NonCanonicalInfinity));
+    }
+}
+
+#[test]
+fn test_bn256_strict_infinity_encoding() {
+    test_strict_infinity_encoding::<Bn256>();
+}
+
+#[test]
+fn test_grumpkin_strict_infinity_encoding() {
+    test_strict_infinity_encoding::<Grumpkin>();
+}
+
+#[test]
+#[should_panic]
+fn test_from_bytes_compressed_panics_on_malformed_bytes() {
+    let _point = Bn256::from_bytes_compressed(&compressed_bytes_with_no_point());
+}
+
+#[test]
+fn test_compressed_round_trip() {
+    let mut rng = rand::thread_rng();
+    let g = <Bn256 as PrimeOrderCurve>::generator();
+    let zero = <Bn256 as PrimeOrderCurve>::zero();
+
+    // --- The layout: flag byte, x-coordinate LE, y-parity byte ---
+    let mut expected_generator_bytes = vec![0_u8; Bn256::COMPRESSED_CURVE_POINT_BYTEWIDTH];
+    expected_generator_bytes[1] = 1;
+    assert_eq!(g.to_bytes_compressed(), expected_generator_bytes);
+    assert_eq!(zero.to_bytes_compressed()[0], 1);
+
+    for point in [zero, g, Bn256::random(&mut rng)] {
+        let bytes = point.to_bytes_compressed();
+        assert_eq!(bytes.len(), Bn256::COMPRESSED_CURVE_POINT_BYTEWIDTH);
+        assert_eq!(Bn256::from_bytes_compressed(&bytes), point);
+    }
+}
+
+#[test]
+fn test_is_on_curve_rejects_invalid_point() {
+    // (1, 3) is not on y^2 = x^3 + 3, while the generator (1, 2) is
+    let point = Bn256 {
+        x: <Bn256 as PrimeOrderCurve>::Base::from(1u64),
+        y: <Bn256 as PrimeOrderCurve>::Base::from(3u64),
+        z: <Bn256 as PrimeOrderCurve>::Base::from(1u64),
+    };
+    assert!(!point.is_on_curve());
+}
+
+#[test]
+fn test_msm_default_matches_override() {
+    let mut rng = rand::thread_rng();
+    let points = (0..40).map(|_| Bn256::random(&mut rng)).collect_vec();
+    let scalars = (0..40)
+        .map(|_| <Bn256 as PrimeOrderCurve>::Scalar::rand(&mut rng))
+        .collect_vec();
+    let naive = scalars.iter().zip(points.iter()).fold(
+        <Bn256 as PrimeOrderCurve>::zero(),
+        |acc, (scalar, point)| acc + *point * *scalar,
+    );
+    assert_eq!(pippenger_msm(&scalars, &points), naive);
+    assert_eq!(<Bn256 as PrimeOrderCurve>::msm(&scalars, &points), naive);
+
+    // --- small scalars only touch the low windows of the default implementation ---
+    let small_scalars = (0..40_u64)
+        .map(<Bn256 as PrimeOrderCurve>::Scalar::from)
+        .collect_vec();
+    assert_eq!(
+        pippenger_msm(&small_scalars, &points),
+        <Bn256 as PrimeOrderCurve>::msm(&small_scalars, &points)
+    );
+}
+
+#[test]
+fn test_msm_edge_cases() {
+    let mut rng = rand::thread_rng();
+    let zero = <Bn256 as PrimeOrderCurve>::zero();
+
+    // --- empty input ---
+    assert_eq!(pippenger_msm::<Bn256>(&[], &[]), zero);
+    assert_eq!(<Bn256 as PrimeOrderCurve>::msm(&[], &[]), zero);
+
+    // --- a single element ---
+    let point = Bn256::random(&mut rng);
+    let scalar = <Bn256 as PrimeOrderCurve>::Scalar::rand(&mut rng);
+    assert_eq!(pippenger_msm(&[scalar], &[point]), point * scalar);
+    assert_eq!(
+        <Bn256 as PrimeOrderCurve>::msm(&[scalar], &[point]),
+        point * scalar
+    );
+
+    // --- zero scalars and identity points ---
+    let scalar_zero = <Bn256 as PrimeOrderCurve>::Scalar::from(0u64);
+    assert_eq!(
+        pippenger_msm(&[scalar_zero, scalar_zero], &[point, point]),
+        zero
+    );
+    assert_eq!(
+        pippenger_msm(&[scalar, scalar], &[zero, point]),
+        point * scalar
+    );
+}
+
+#[test]
+fn test_check_encoding_consistency() {
+    let mut rng = rand::thread_rng();
+    let generator = <Bn256 as PrimeOrderCurve>::generator();
+    // --- the identity; the generator (1, 2), whose y is the smaller root and even; its negation
+    // (1, p - 2), whose y is odd; and points with and without normalized coordinates ---
+    let mut points = vec![
+        <Bn256 as PrimeOrderCurve>::zero(),
+        generator,
+        -generator,
+        generator + generator,
+        -(generator + generator),
+    ];
+    points.extend((0..32).map(|_| Bn256::random(&mut rng)));
+    assert_eq!(check_encoding_consistency(&points), Ok(()));
+    let grumpkin_points = (0..8).map(|_| Grumpkin::random(&mut rng)).collect_vec();
+    assert_eq!(check_encoding_consistency(&grumpkin_points), Ok(()));
+
+    // --- (1, 3) is off the curve: its compressed encoding decodes to (1, 2) or (1, -2). Under the
+    // `paranoid` feature the encoders' own assertion fires first. ---
+    #[cfg(not(feature = "paranoid"))]
+    {
+        points.push(Bn256 {
+            x: <Bn256 as PrimeOrderCurve>::Base::from(1u64),
+            y: <Bn256 as PrimeOrderCurve>::Base::from(3u64),
+            z: <Bn256 as PrimeOrderCurve>::Base::from(1u64),
+        });
+        assert_eq!(
+            check_encoding_consistency(&points),
+            Err(HyraxError::InconsistentPointEncoding(37))
+        );
+    }
+}
+
+#[test]
+fn test_encode_checked() {
+    type Base = <Bn256 as PrimeOrderCurve>::Base;
+    let mut rng = rand::thread_rng();
+    let point = Bn256::random(&mut rng);
+    let (x, y) = point.affine_coordinates().unwrap();
+
+    // --- (x z^2, y z^3, z) represents the same point for every nonzero z ---
+    let z = Base::from(7u64);
+    let representative = Bn256 {
+        x: x * z.square(),
+        y: y * z.square() * z,
+        z,
+    };
+    assert_eq!(representative, point);
+    assert_eq!(
+        encode_checked_compressed(&representative),
+        Ok(point.to_bytes_compressed())
+    );
+    assert_eq!(
+        encode_checked_uncompressed(&representative),
+        Ok(point.to_bytes_uncompressed())
+    );
+
+    // --- any representative with z = 0 is the identity ---
+    let identity = Bn256 {
+        x: Base::from(5u64),
+        y: Base::from(9u64),
+        z: Base::zero(),
+    };
+    assert_eq!(
+        encode_checked_compressed(&identity),
+        Ok(<Bn256 as PrimeOrderCurve>::zero().to_bytes_compressed())
+    );
+
+    // --- (x z^2, (y + 1) z^3, z) normalizes to (x, y + 1), which is off the curve: its compressed
+    // encoding decodes to a different point, and its uncompressed one not at all. Under the
+    // `paranoid` feature the encoders' own assertion fires first. ---
+    #[cfg(not(feature = "paranoid"))]
+    {
+        let off_curve = Bn256 {
+            x: x * z.square(),
+            y: (y + Base::one()) * z.square() * z,
+            z,
+        };
+        assert_eq!(
+            encode_checked_compressed(&off_curve),
+            Err(HyraxError::EncodingRoundTripMismatch)
+        );
+        assert_eq!(
+            encode_checked_uncompressed(&off_curve),
+            Err(HyraxError::EncodingRoundTripMismatch)
+        );
+    }
 }
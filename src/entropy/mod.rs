@@ -0,0 +1,111 @@
+use crate::error::HyraxError;
+use blake2::{Blake2s256, Digest};
+use rand_core::{OsRng, RngCore};
+use std::sync::Mutex;
+
+#[cfg(test)]
+pub mod tests;
+
+/// Where a `BlindingSeed` came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedSource {
+    /// Sampled from the operating system's entropy source by `production_seed`.
+    Production,
+    /// Derived from a public label by `test_seed`; such a seed does not hide anything.
+    Test,
+    /// Raw bytes supplied by the caller (e.g. a seed restored from escrow), via `From<[u8; 32]>`.
+    Supplied,
+}
+
+/// The 32-byte seed from which the blinding factors of a commitment are derived, tagged with the
+/// source that produced it, so that the byte-level entry points (e.g.
+/// `compute_commitments_binary_outputs`) can report it under the `tracing` feature.
+/// Its `Debug` output shows only the source, never the seed itself.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct BlindingSeed {
+    bytes: [u8; 32],
+    source: SeedSource,
+}
+
+impl BlindingSeed {
+    /// The seed itself.
+    pub fn bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Where the seed came from.
+    pub fn source(&self) -> SeedSource {
+        self.source
+    }
+}
+
+impl From<[u8; 32]> for BlindingSeed {
+    fn from(bytes: [u8; 32]) -> Self {
+        BlindingSeed {
+            bytes,
+            source: SeedSource::Supplied,
+        }
+    }
+}
+
+impl std::fmt::Debug for BlindingSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlindingSeed")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Domain separator for `test_seed`, so that test seeds can never collide with
+/// anything derived elsewhere from the same label.
+const TEST_SEED_DOMAIN: &[u8] = b"hyrax-pcs-commit TEST ONLY blinding seed";
+
+// The last seed returned by `production_seed`, used for the repeated-output health check.
+static PREVIOUS_PRODUCTION_SEED: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+/// Samples a blinding factor seed from the operating system's entropy source
+/// (`OsRng`, i.e. `getrandom`) and runs a basic health check on it: the seed
+/// must not be all zeros, and must differ from the seed returned by the previous
+/// call. This is the only seed source that should be used in production.
+pub fn production_seed() -> Result<BlindingSeed, HyraxError> {
+    let mut seed = [0u8; 32];
+    OsRng
+        .try_fill_bytes(&mut seed)
+        .map_err(|err| HyraxError::EntropyFailure(err.to_string()))?;
+
+    let mut previous = PREVIOUS_PRODUCTION_SEED.lock().unwrap();
+    check_seed_health(&seed, previous.as_ref())?;
+    *previous = Some(seed);
+    Ok(BlindingSeed {
+        bytes: seed,
+        source: SeedSource::Production,
+    })
+}
+
+/// Derives a deterministic seed from `label`. FOR TESTS AND EXAMPLES ONLY: anyone
+/// who knows the label can recompute the seed, and with it every blinding factor,
+/// so commitments made with it do not hide the committed data.
+pub fn test_seed(label: &str) -> BlindingSeed {
+    let mut hasher = Blake2s256::new();
+    hasher.update(TEST_SEED_DOMAIN);
+    hasher.update(label.as_bytes());
+    BlindingSeed {
+        bytes: hasher.finalize().into(),
+        source: SeedSource::Test,
+    }
+}
+
+// Rejects seeds that are all zeros or repeat the previously returned seed.
+fn check_seed_health(seed: &[u8; 32], previous: Option<&[u8; 32]>) -> Result<(), HyraxError> {
+    if seed.iter().all(|byte| *byte == 0) {
+        return Err(HyraxError::EntropyFailure(
+            "entropy source returned an all-zero seed".to_string(),
+        ));
+    }
+    if previous == Some(seed) {
+        return Err(HyraxError::EntropyFailure(
+            "entropy source returned the same seed twice".to_string(),
+        ));
+    }
+    Ok(())
+}
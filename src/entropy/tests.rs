@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn test_production_seeds_pass_health_check_and_differ() {
+    let seed_1 = production_seed().unwrap();
+    let seed_2 = production_seed().unwrap();
+    assert_ne!(seed_1.bytes(), seed_2.bytes());
+    assert_eq!(seed_1.source(), SeedSource::Production);
+}
+
+#[test]
+fn test_health_check_rejects_zero_seed() {
+    assert!(matches!(
+        check_seed_health(&[0u8; 32], None),
+        Err(HyraxError::EntropyFailure(_))
+    ));
+}
+
+#[test]
+fn test_health_check_rejects_repeated_seed() {
+    let seed = [42u8; 32];
+    assert_eq!(check_seed_health(&seed, None), Ok(()));
+    assert!(matches!(
+        check_seed_health(&seed, Some(&seed)),
+        Err(HyraxError::EntropyFailure(_))
+    ));
+}
+
+#[test]
+fn test_test_seed_is_deterministic_per_label() {
+    assert_eq!(test_seed("iris"), test_seed("iris"));
+    assert_ne!(test_seed("iris"), test_seed("mask"));
+    assert_eq!(test_seed("iris").source(), SeedSource::Test);
+}
+
+#[test]
+fn test_seed_debug_hides_the_bytes() {
+    let seed = BlindingSeed::from([42u8; 32]);
+    assert_eq!(seed.source(), SeedSource::Supplied);
+    assert_eq!(format!("{seed:?}"), "BlindingSeed { source: Supplied, .. }");
+}
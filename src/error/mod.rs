@@ -0,0 +1,136 @@
+use thiserror::Error;
+
+/// Errors returned by the fallible parts of the Hyrax commitment API.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HyraxError {
+    /// The byte length of a serialized commitment is not a multiple of the
+    /// serialized curve point width.
+    #[error("commitment byte length {len} is not a multiple of the point width {point_width}")]
+    InvalidCommitmentLength { len: usize, point_width: usize },
+    /// The number of rows in a serialized commitment is not a power of two,
+    /// which the Hyrax matrix layout requires.
+    #[error("commitment has {0} rows, which is not a power of two")]
+    NonPowerOfTwoRows(usize),
+    /// The data is longer than the maximum supported input length.
+    #[error("input of {len} bytes exceeds the maximum supported input length of {max} bytes")]
+    InputTooLarge { len: usize, max: usize },
+    /// The data is empty, which a commitment that does not pad it cannot represent.
+    #[error("input is empty")]
+    EmptyInput,
+    /// The (padded) data does not fill a whole number of rows of the commitment matrix.
+    #[error("data of length {data_len} does not fill a whole number of rows of {n_cols} columns")]
+    InvalidMatrixShape { data_len: usize, n_cols: usize },
+    /// The digest recorded in a setup manifest does not match the manifest's contents.
+    #[error("setup manifest digest does not match its contents")]
+    SetupManifestDigestMismatch,
+    /// A setup manifest is internally consistent but does not describe a valid setup.
+    #[error("invalid setup manifest: {0}")]
+    InvalidSetupManifest(String),
+    /// The entropy source failed, or produced a seed that failed the health check.
+    #[error("entropy source failure: {0}")]
+    EntropyFailure(String),
+    /// An evaluation point does not have one coordinate per variable of the committed matrix.
+    #[error("evaluation point has {point_len} coordinates, expected {expected}")]
+    InvalidEvaluationPointLength { point_len: usize, expected: usize },
+    /// The byte length of a serialized vector of scalars is not a multiple of the
+    /// serialized scalar width.
+    #[error("scalar buffer length {len} is not a multiple of the scalar width {scalar_width}")]
+    InvalidScalarBufferLength { len: usize, scalar_width: usize },
+    /// A sliding window is wider than the committer, or its stride is zero.
+    #[error(
+        "invalid sliding window of width {window} and stride {stride} for {n_cols} generators"
+    )]
+    InvalidWindow {
+        window: usize,
+        stride: usize,
+        n_cols: usize,
+    },
+    /// The number of blinding factors does not match the number of rows of the commitment.
+    #[error("expected {expected} blinding factors, one per row, but got {actual}")]
+    BlindingFactorCountMismatch { expected: usize, actual: usize },
+    /// The data and the public baseline it is committed relative to have different lengths.
+    #[error("data of length {data_len} does not match the baseline of length {baseline_len}")]
+    BaselineLengthMismatch {
+        data_len: usize,
+        baseline_len: usize,
+    },
+    /// A serialized value does not have the byte length its encoding requires.
+    #[error("expected {expected} bytes, got {got}")]
+    InvalidByteLength { expected: usize, got: usize },
+    /// Serialized coordinates do not describe a point on the curve.
+    #[error("serialized point is not on the curve")]
+    PointNotOnCurve,
+    /// A compressed point's x-coordinate is not that of any point on the curve.
+    #[error("serialized x-coordinate is not that of a point on the curve")]
+    XCoordinateNotOnCurve,
+    /// A serialized coordinate is not reduced modulo the field order.
+    #[error("serialized coordinate is not reduced modulo the field order")]
+    NonCanonicalCoordinate,
+    /// A serialized scalar (at the given index) is not reduced modulo the scalar field order.
+    #[error("serialized scalar {0} is not reduced modulo the field order")]
+    NonCanonicalScalar(usize),
+    /// A message has more elements than the committer has generators.
+    #[error("message of length {got} exceeds the committer's capacity of {capacity} generators")]
+    MessageTooLong { capacity: usize, got: usize },
+    /// The leading flag byte of a serialized point is neither 0 (affine point) nor 1 (infinity).
+    #[error("invalid point flag byte {0}, expected 0 or 1")]
+    InvalidPointFlag(u8),
+    /// A serialized point at infinity has nonzero bytes after its flag byte.
+    #[error("serialized point at infinity has nonzero trailing bytes")]
+    NonCanonicalInfinity,
+    /// Bytes of the right length could not be decoded for some other reason.
+    #[error("deserialization error: {0}")]
+    DeserializationError(String),
+    /// Integers of the requested bitwidth would not all be below the scalar field modulus, so
+    /// distinct integers could commit to the same field element.
+    #[error("integer bitwidth {bitwidth} is not below the scalar field's {field_bits} bits")]
+    BitwidthExceedsField { bitwidth: usize, field_bits: usize },
+    /// A serialized commitment does not have one row commitment per row of the data.
+    #[error("expected {expected} row commitments, one per row, but got {actual}")]
+    RowCountMismatch { expected: usize, actual: usize },
+    /// A row commitment does not open to the corresponding row of the data.
+    #[error("row {0} does not match its commitment")]
+    RowCommitmentMismatch(usize),
+    /// The iris code and the mask of a joint commitment have different lengths.
+    #[error("iris code of length {iris_len} does not match the mask of length {mask_len}")]
+    ChannelLengthMismatch { iris_len: usize, mask_len: usize },
+    /// A joint commitment layout has more column variables than its joint data has variables,
+    /// i.e. the joint data does not fill a single row.
+    #[error(
+        "joint layout has {log_num_cols} column variables but only {num_joint_vars} variables"
+    )]
+    InvalidJointLayout {
+        log_num_cols: usize,
+        num_joint_vars: usize,
+    },
+    /// Committing would need more working memory than the caller's budget allows.
+    #[error(
+        "commitment needs an estimated {required} bytes of memory, over the budget of {budget}"
+    )]
+    MemoryBudgetExceeded { required: usize, budget: usize },
+    /// A point does not decode back to itself from both its compressed and its uncompressed
+    /// encoding.
+    #[error("point {0} does not round-trip through its compressed and uncompressed encodings")]
+    InconsistentPointEncoding(usize),
+    /// A point's encoding does not decode back to the point, see `encode_checked_compressed`.
+    #[error("point does not decode back to itself from its encoding")]
+    EncodingRoundTripMismatch,
+    /// A thread count of 0 was requested.
+    #[error("the number of threads must be positive")]
+    InvalidThreadCount,
+    /// The dedicated thread pool could not be built.
+    #[error("failed to build the thread pool: {0}")]
+    ThreadPoolBuild(String),
+    /// The threshold of a `ThresholdCommitter` is not between 1 and the number of parties.
+    #[error("threshold {threshold} is not between 1 and the number of parties {num_parties}")]
+    InvalidThreshold {
+        threshold: usize,
+        num_parties: usize,
+    },
+    /// Fewer shares than the threshold were supplied to reconstruct a blinding factor.
+    #[error("{got} shares are fewer than the threshold of {threshold}")]
+    InsufficientShares { threshold: usize, got: usize },
+    /// A blinding factor share's index is out of range or repeated.
+    #[error("share index {0} is out of range or repeated")]
+    InvalidShareIndex(u64),
+}
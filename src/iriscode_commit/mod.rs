@@ -1,20 +1,40 @@
 pub mod tests;
 
-use super::curves::PrimeOrderCurve;
+use super::curves::{
+    canonical_from_le_bytes, encode_checked_compressed, encode_checked_uncompressed,
+    PrimeOrderCurve,
+};
+use crate::entropy::BlindingSeed;
+use crate::error::HyraxError;
 use crate::pedersen::PedersenCommitter;
+use crate::row_commit_cache::RowCommitCache;
+use crate::scalar_serde::{deserialize_scalars, serialize_scalars};
+use ark_bn254::Fq as Bn256Base;
 use ark_bn254::Fr as Bn256Scalar;
+use ark_bn254::G1Affine;
 use ark_bn254::G1Projective as Bn256Point;
-use ark_ff::BigInteger;
 use ark_ff::PrimeField;
 use ark_ff::UniformRand;
+use ark_ff::Zero;
+use blake2::{Blake2s256, Digest};
 use itertools::Itertools;
+use rand::RngCore;
 use rand::SeedableRng;
-use rand_chacha::ChaCha20Rng;
+use rand_chacha::{ChaCha12Rng, ChaCha20Rng, ChaCha8Rng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 // log of the number of columns in the re-arrangement of the image as a matrix
 pub const LOG_NUM_COLS: usize = 9;
+// the largest supported data length in bytes (64 MiB); larger inputs are rejected up front
+// rather than failing on memory exhaustion partway through
+pub const MAX_INPUT_LEN: usize = 1 << 26;
 // public string used to derive the generators (arbitrary constant)
 pub const PUBLIC_STRING: &str = "Modulus <3 Worldcoin: ZKML Self-Custody Edition";
+// public string used to derive the generators for committing to the blinding factors; distinct
+// from PUBLIC_STRING so that the two generator sets are independent
+pub const BLINDING_COMMITMENT_PUBLIC_STRING: &str =
+    "Modulus <3 Worldcoin: Hyrax Blinding Factor Commitment";
 
 /// The Hyrax polynomial commitment scheme returns two things:
 /// * The `commitment` itself, to be signed by the Orb and sent to Worldcoin's
@@ -27,12 +47,234 @@ pub struct HyraxCommitmentOutput<C: PrimeOrderCurve> {
     pub blinding_factors: Vec<C::Scalar>,
 }
 
+/// A single row of a `HyraxCommitmentOutput`: the row's index in the matrix,
+/// its commitment, and the blinding factor used to produce it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowRecord<'a, C: PrimeOrderCurve> {
+    pub index: usize,
+    pub commitment: &'a C,
+    pub blinding_factor: &'a C::Scalar,
+}
+
+impl<C: PrimeOrderCurve> HyraxCommitmentOutput<C> {
+    /// Iterates over the rows of the commitment in matrix (and serialization) order.
+    pub fn rows(&self) -> impl Iterator<Item = RowRecord<'_, C>> {
+        self.commitment
+            .iter()
+            .zip_eq(self.blinding_factors.iter())
+            .enumerate()
+            .map(|(index, (commitment, blinding_factor))| RowRecord {
+                index,
+                commitment,
+                blinding_factor,
+            })
+    }
+
+    /// Returns the row at `index`, or `None` if it is out of bounds.
+    pub fn get_row(&self, index: usize) -> Option<RowRecord<'_, C>> {
+        let commitment = self.commitment.get(index)?;
+        let blinding_factor = self.blinding_factors.get(index)?;
+        Some(RowRecord {
+            index,
+            commitment,
+            blinding_factor,
+        })
+    }
+}
+
+/// Consumes the output, yielding `(row_index, row_commitment, blinding_factor)` triples.
+impl<C: PrimeOrderCurve> IntoIterator for HyraxCommitmentOutput<C> {
+    type Item = (usize, C, C::Scalar);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commitment
+            .into_iter()
+            .zip_eq(self.blinding_factors)
+            .enumerate()
+            .map(|(index, (commitment, blinding_factor))| (index, commitment, blinding_factor))
+            .collect_vec()
+            .into_iter()
+    }
+}
+
+/// Serializes as the number of rows (as a u64, like arkworks' `Vec`s), followed by the row
+/// commitments as their `to_bytes_compressed` and the blinding factors as `serialize_scalars`,
+/// i.e. exactly the bytes of the crate's own serialization. The `Compress` mode is ignored, since
+/// the crate's point encoding is always compressed.
+/// Deserialization fails with `SerializationError::InvalidData` on a malformed point encoding, and
+/// so does serialization under the `paranoid` feature if a row commitment does not round-trip.
+#[cfg(feature = "ark-serialize")]
+impl<C: PrimeOrderCurve> ark_serialize::CanonicalSerialize for HyraxCommitmentOutput<C> {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: ark_serialize::Compress,
+    ) -> Result<(), ark_serialize::SerializationError> {
+        (self.commitment.len() as u64).serialize_with_mode(&mut writer, compress)?;
+        for row_commitment in self.commitment.iter() {
+            let bytes = encode_row_commitment(row_commitment, PointEncoding::Compressed)
+                .map_err(|_| ark_serialize::SerializationError::InvalidData)?;
+            writer.write_all(&bytes)?;
+        }
+        writer.write_all(&serialize_scalars::<C>(&self.blinding_factors))?;
+        Ok(())
+    }
+
+    fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+        (self.commitment.len() as u64).serialized_size(compress)
+            + self.commitment.len() * C::COMPRESSED_CURVE_POINT_BYTEWIDTH
+            + self.blinding_factors.len() * C::SCALAR_ELEM_BYTEWIDTH
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl<C: PrimeOrderCurve> ark_serialize::Valid for HyraxCommitmentOutput<C> {
+    fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+        if self.commitment.len() != self.blinding_factors.len()
+            || !self.commitment.len().is_power_of_two()
+            || !self.commitment.iter().all(|point| point.is_on_curve())
+        {
+            return Err(ark_serialize::SerializationError::InvalidData);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl<C: PrimeOrderCurve> ark_serialize::CanonicalDeserialize for HyraxCommitmentOutput<C> {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: ark_serialize::Compress,
+        validate: ark_serialize::Validate,
+    ) -> Result<Self, ark_serialize::SerializationError> {
+        let n_rows = u64::deserialize_with_mode(&mut reader, compress, validate)? as usize;
+        // no commitment to at most MAX_INPUT_LEN bytes has more rows
+        if n_rows > MAX_INPUT_LEN {
+            return Err(ark_serialize::SerializationError::InvalidData);
+        }
+
+        // --- Grow the vectors as the rows are actually read, so that a forged row count cannot
+        // force a large allocation upfront ---
+        let mut commitment = vec![];
+        let mut point_bytes = vec![0u8; C::COMPRESSED_CURVE_POINT_BYTEWIDTH];
+        for _ in 0..n_rows {
+            reader.read_exact(&mut point_bytes)?;
+            commitment.push(
+                C::try_from_bytes_compressed(&point_bytes)
+                    .map_err(|_| ark_serialize::SerializationError::InvalidData)?,
+            );
+        }
+        let mut blinding_factors = vec![];
+        let mut scalar_bytes = vec![0u8; C::SCALAR_ELEM_BYTEWIDTH];
+        for _ in 0..n_rows {
+            reader.read_exact(&mut scalar_bytes)?;
+            blinding_factors.push(
+                canonical_from_le_bytes(&scalar_bytes)
+                    .map_err(|_| ark_serialize::SerializationError::InvalidData)?,
+            );
+        }
+
+        let output = HyraxCommitmentOutput {
+            commitment,
+            blinding_factors,
+        };
+        if validate == ark_serialize::Validate::Yes {
+            ark_serialize::Valid::check(&output)?;
+        }
+        Ok(output)
+    }
+}
+
+/// Serializes as the `HyraxCommitmentOutputSerialized` of the output, i.e. the row commitments'
+/// `to_bytes_compressed` and the blinding factors' `serialize_scalars`, so that any serde format
+/// can carry it and its byte fields match those of `compute_commitments_binary_outputs`.
+/// Under the `paranoid` feature, fails if a row commitment does not decode back from its bytes.
+impl<C: PrimeOrderCurve> Serialize for HyraxCommitmentOutput<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        let mut commitment_serialized = vec![];
+        for row_commitment in self.commitment.iter() {
+            commitment_serialized.extend(
+                encode_row_commitment(row_commitment, PointEncoding::Compressed)
+                    .map_err(S::Error::custom)?,
+            );
+        }
+        HyraxCommitmentOutputSerialized {
+            commitment_serialized,
+            blinding_factors_serialized: serialize_scalars::<C>(&self.blinding_factors),
+            point_encoding: PointEncoding::Compressed,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Inverse of the `Serialize` impl, accepting either recorded `point_encoding`.
+/// Fails on a malformed point encoding, or if the number of row commitments and blinding factors
+/// differ.
+impl<'de, C: PrimeOrderCurve> Deserialize<'de> for HyraxCommitmentOutput<C> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let serialized = HyraxCommitmentOutputSerialized::deserialize(deserializer)?;
+        let commitment = deserialize_commitment_with_encoding::<C>(
+            &serialized.commitment_serialized,
+            serialized.point_encoding,
+        )
+        .map_err(D::Error::custom)?;
+        let blinding_factors = deserialize_scalars::<C>(&serialized.blinding_factors_serialized)
+            .map_err(D::Error::custom)?;
+        if commitment.len() != blinding_factors.len() {
+            return Err(D::Error::custom(HyraxError::BlindingFactorCountMismatch {
+                expected: commitment.len(),
+                actual: blinding_factors.len(),
+            }));
+        }
+        Ok(HyraxCommitmentOutput {
+            commitment,
+            blinding_factors,
+        })
+    }
+}
+
+/// The encoding of the row commitments in a `HyraxCommitmentOutputSerialized`.
+/// Compressed points are half the size, but decoding each one takes a square root; uncompressed
+/// points decode with just an on-curve check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PointEncoding {
+    #[default]
+    Compressed,
+    Uncompressed,
+}
+
+impl PointEncoding {
+    /// Returns the width of an encoded point.
+    pub fn point_width<C: PrimeOrderCurve>(&self) -> usize {
+        match self {
+            PointEncoding::Compressed => C::COMPRESSED_CURVE_POINT_BYTEWIDTH,
+            PointEncoding::Uncompressed => C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH,
+        }
+    }
+}
+
 /// The concrete serialized version of `HyraxCommitmentOutput` to be used by
 /// the Orb!
+/// `point_encoding` records how `commitment_serialized` is encoded; it defaults to
+/// `PointEncoding::Compressed` when absent, as in outputs serialized before it was recorded.
 #[derive(Serialize, Deserialize)]
 pub struct HyraxCommitmentOutputSerialized {
     pub commitment_serialized: Vec<u8>,
     pub blinding_factors_serialized: Vec<u8>,
+    #[serde(default)]
+    pub point_encoding: PointEncoding,
+}
+
+impl HyraxCommitmentOutputSerialized {
+    /// Deserializes the row commitments according to the recorded `point_encoding`.
+    pub fn deserialize_commitment(&self) -> Result<Vec<Bn256Point>, HyraxError> {
+        deserialize_commitment_with_encoding(&self.commitment_serialized, self.point_encoding)
+    }
 }
 
 /// Wrapper function around `compute_commitments` instantiated
@@ -44,32 +286,211 @@ pub struct HyraxCommitmentOutputSerialized {
 /// and the mask as `data` from the Orb!
 pub fn compute_commitments_binary_outputs(
     data: &[u8],
-    blinding_factor_seed: [u8; 32],
-) -> HyraxCommitmentOutputSerialized {
+    blinding_factor_seed: impl Into<BlindingSeed>,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    binary_outputs(
+        data,
+        blinding_factor_seed.into(),
+        LOG_NUM_COLS,
+        PointEncoding::Compressed,
+    )
+}
+
+/// Alias of `compute_commitments_binary_outputs_with_shape`.
+pub fn compute_commitments_binary_outputs_with_params(
+    data: &[u8],
+    blinding_factor_seed: impl Into<BlindingSeed>,
+    log_num_cols: usize,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    compute_commitments_binary_outputs_with_shape(data, blinding_factor_seed, log_num_cols)
+}
+
+/// Same as `compute_commitments_binary_outputs`, arranging the data as a matrix of
+/// `1 << log_num_cols` columns instead of `1 << LOG_NUM_COLS`.
+/// Unlike `compute_commitments_binary_outputs`, which keeps its original behaviour for inputs
+/// shorter than a row, this errors with `HyraxError::InvalidMatrixShape` if the padded data does
+/// not fill a whole number of rows (i.e. unless `data.len().next_power_of_two()` is divisible by
+/// `1 << log_num_cols`), or if `log_num_cols == 0`, i.e. for a single-column matrix whose "rows"
+/// are the individual elements.
+pub fn compute_commitments_binary_outputs_with_shape(
+    data: &[u8],
+    blinding_factor_seed: impl Into<BlindingSeed>,
+    log_num_cols: usize,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    let padded_len = validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    let n_cols = 1usize
+        .checked_shl(log_num_cols as u32)
+        .unwrap_or(usize::MAX);
+    if log_num_cols == 0 || log_num_cols >= usize::BITS as usize {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: padded_len,
+            n_cols,
+        });
+    }
+    validate_matrix_shape(padded_len, n_cols)?;
+    binary_outputs(
+        data,
+        blinding_factor_seed.into(),
+        log_num_cols,
+        PointEncoding::Compressed,
+    )
+}
+
+/// Same as `compute_commitments_binary_outputs`, encoding the row commitments with
+/// `point_encoding` (which the output records) instead of always compressing them.
+pub fn compute_commitments_binary_outputs_with_encoding(
+    data: &[u8],
+    blinding_factor_seed: impl Into<BlindingSeed>,
+    point_encoding: PointEncoding,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    binary_outputs(
+        data,
+        blinding_factor_seed.into(),
+        LOG_NUM_COLS,
+        point_encoding,
+    )
+}
+
+// Pre: log_num_cols < usize::BITS
+fn binary_outputs(
+    data: &[u8],
+    blinding_factor_seed: BlindingSeed,
+    log_num_cols: usize,
+    point_encoding: PointEncoding,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
     // --- Compute the generators from the given `PUBLIC_STRING` ---
     let vector_committer: PedersenCommitter<Bn256Point> =
-        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+        PedersenCommitter::new(1 << log_num_cols, PUBLIC_STRING);
+
+    binary_outputs_with_committer(
+        data,
+        blinding_factor_seed,
+        &vector_committer,
+        point_encoding,
+    )
+}
+
+/// Same as `compute_commitments_binary_outputs`, with a prebuilt committer (e.g. one loaded with
+/// `PedersenCommitter::from_bytes`) instead of sampling the generators from `PUBLIC_STRING` on
+/// every call. The matrix has one column per generator of the committer.
+/// Errors with `HyraxError::InvalidMatrixShape` if the padded data does not fill a whole number
+/// of rows.
+pub fn compute_commitments_binary_outputs_with_committer(
+    data: &[u8],
+    blinding_factor_seed: impl Into<BlindingSeed>,
+    vector_committer: &PedersenCommitter<Bn256Point>,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    let padded_len = validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    validate_matrix_shape(padded_len, vector_committer.generators.len())?;
+    binary_outputs_with_committer(
+        data,
+        blinding_factor_seed.into(),
+        vector_committer,
+        PointEncoding::Compressed,
+    )
+}
+
+// Errors with `HyraxError::InvalidMatrixShape` unless the padded data fills a whole, nonzero
+// number of rows of `n_cols` columns.
+fn validate_matrix_shape(padded_len: usize, n_cols: usize) -> Result<(), HyraxError> {
+    if n_cols == 0 || padded_len % n_cols != 0 {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: padded_len,
+            n_cols,
+        });
+    }
+    Ok(())
+}
+
+// Encodes a row commitment as `point_encoding`. Under the `paranoid` feature, the encoding is
+// checked to decode back to the commitment, see `encode_checked_compressed`.
+fn encode_row_commitment<C: PrimeOrderCurve>(
+    row_commitment: &C,
+    point_encoding: PointEncoding,
+) -> Result<Vec<u8>, HyraxError> {
+    if cfg!(feature = "paranoid") {
+        match point_encoding {
+            PointEncoding::Compressed => encode_checked_compressed(row_commitment),
+            PointEncoding::Uncompressed => encode_checked_uncompressed(row_commitment),
+        }
+    } else {
+        Ok(match point_encoding {
+            PointEncoding::Compressed => row_commitment.to_bytes_compressed(),
+            PointEncoding::Uncompressed => row_commitment.to_bytes_uncompressed(),
+        })
+    }
+}
+
+fn binary_outputs_with_committer(
+    data: &[u8],
+    blinding_factor_seed: BlindingSeed,
+    vector_committer: &PedersenCommitter<Bn256Point>,
+    point_encoding: PointEncoding,
+) -> Result<HyraxCommitmentOutputSerialized, HyraxError> {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        seed_source = ?blinding_factor_seed.source(),
+        data_len = data.len(),
+        "committing with a blinding factor seed"
+    );
 
     // --- Compute the commitment and blinding factors ---
-    let HyraxCommitmentOutput {
-        commitment,
-        blinding_factors,
-    } = compute_commitments(data, &vector_committer, blinding_factor_seed);
+    let commitment_output =
+        compute_commitments(data, vector_committer, blinding_factor_seed.bytes())?;
 
-    // --- Serialize into bytes ---
-    let commitment_serialized: Vec<u8> = commitment
-        .iter()
-        .flat_map(|element| element.to_bytes_compressed())
-        .collect_vec();
-    let blinding_factors_serialized: Vec<u8> = blinding_factors
-        .iter()
-        .flat_map(|element| element.into_bigint().to_bytes_le())
-        .collect_vec();
+    // --- Serialize into bytes, row by row ---
+    let mut commitment_serialized: Vec<u8> = vec![];
+    for row in commitment_output.rows() {
+        commitment_serialized.extend(encode_row_commitment(row.commitment, point_encoding)?);
+    }
+    let blinding_factors_serialized =
+        serialize_scalars::<Bn256Point>(&commitment_output.blinding_factors);
+    debug_assert!(
+        point_encoding == PointEncoding::Uncompressed
+            || is_canonical_serialization::<Bn256Point>(&commitment_serialized)
+    );
 
-    HyraxCommitmentOutputSerialized {
+    Ok(HyraxCommitmentOutputSerialized {
         commitment_serialized,
         blinding_factors_serialized,
+        point_encoding,
+    })
+}
+
+/// A commitment in the form in which the Orb signs it: `commitment_bytes` are the rows'
+/// compressed (normalized, and therefore deterministic) encodings, exactly as transmitted and
+/// deserialized, and `digest` is their Blake2s256 digest, ready to be fed to a signer.
+pub struct SignableCommitment<C: PrimeOrderCurve> {
+    pub commitment_bytes: Vec<u8>,
+    pub digest: [u8; 32],
+    pub blinding_factors: Vec<C::Scalar>,
+}
+
+/// Computes the commitment as `compute_commitments` does and prepares it for signing.
+/// Post: result.commitment_bytes == the concatenated `to_bytes_compressed` of the rows, which for
+///     BN254 and the default committer is `compute_commitments_binary_outputs(..).commitment_serialized`.
+/// Under the `paranoid` feature, errors with `HyraxError::EncodingRoundTripMismatch` if a row
+/// commitment does not decode back from its bytes.
+pub fn commit_for_signing<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<SignableCommitment<C>, HyraxError> {
+    let commitment_output = compute_commitments(data, vector_committer, blinding_factor_seed)?;
+    let mut commitment_bytes = vec![];
+    for row_commitment in commitment_output.commitment.iter() {
+        commitment_bytes.extend(encode_row_commitment(
+            row_commitment,
+            PointEncoding::Compressed,
+        )?);
     }
+    let digest: [u8; 32] = Blake2s256::digest(&commitment_bytes).into();
+
+    Ok(SignableCommitment {
+        commitment_bytes,
+        digest,
+        blinding_factors: commitment_output.blinding_factors,
+    })
 }
 
 // this function computes the commitments to the rows of the matrix. essentially, this is the vector of
@@ -79,67 +500,1601 @@ pub fn compute_commitments_binary_outputs(
 /// Returns a vector of commitments, one for each chunk of the data
 /// (i.e. each row of the data when arranged as a matrix).
 /// Blinding factors are generated using the provided seed and the ChaCha20Rng.
+/// Errors with `HyraxError::InputTooLarge` if data.len() > MAX_INPUT_LEN.
 /// Pre: data.len().is_power_of_two()
 /// Post: result.len() == data.len() / vector_committer.generators.len()
 pub fn compute_commitments<C: PrimeOrderCurve>(
     data: &[u8],
     vector_committer: &PedersenCommitter<C>,
     blinding_factor_seed: [u8; 32],
-) -> HyraxCommitmentOutput<C> {
-    // pad the data to the nearest power of 2 by appending 0s
-    let nearest_power_of_2_len = data.len().next_power_of_two();
-    let padding_amount = nearest_power_of_2_len - data.len();
-    let mut data_vec = data.to_vec();
-    let padding_vec = vec![0; padding_amount];
-    data_vec.extend(padding_vec.iter());
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    compute_commitments_with_max_len(data, vector_committer, blinding_factor_seed, MAX_INPUT_LEN)
+}
+
+/// Same as `compute_commitments`, with a caller-chosen bound on data.len() instead of
+/// `MAX_INPUT_LEN`.
+pub fn compute_commitments_with_max_len<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    max_len: usize,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    commit_rows(
+        data,
+        vector_committer,
+        blinding_factor_seed,
+        max_len,
+        RngKind::default(),
+    )
+}
+
+/// Same as `compute_commitments`, committing to the rows on at most `max_threads` threads (of a
+/// dedicated thread pool) instead of on rayon's global pool, which uses one thread per core.
+/// The result does not depend on the number of threads.
+/// Each call builds (and tears down) its own pool, spawning `max_threads` threads; a caller
+/// committing repeatedly should build a pool once and use `compute_commitments_in_pool`.
+/// Errors with `HyraxError::InvalidThreadCount` if `max_threads == 0`, and with
+/// `HyraxError::ThreadPoolBuild` if the pool cannot be built.
+#[cfg(feature = "rayon")]
+pub fn compute_commitments_with_max_threads<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    max_threads: usize,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    if max_threads == 0 {
+        return Err(HyraxError::InvalidThreadCount);
+    }
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(max_threads)
+        .build()
+        .map_err(|err| HyraxError::ThreadPoolBuild(err.to_string()))?;
+    compute_commitments_in_pool(data, vector_committer, blinding_factor_seed, &thread_pool)
+}
+
+/// Same as `compute_commitments`, committing to the rows on the threads of `thread_pool`.
+#[cfg(feature = "rayon")]
+pub fn compute_commitments_in_pool<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    thread_pool: &rayon::ThreadPool,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    thread_pool.install(|| compute_commitments(data, vector_committer, blinding_factor_seed))
+}
+
+/// Same as `compute_commitments`, drawing the blinding factors from the ChaCha variant selected
+/// by `rng_kind` instead of always using ChaCha20.
+/// Note that the variant is part of what makes a commitment reproducible: the same seed yields a
+/// different stream (and therefore different blinding factors) under each variant, so whoever
+/// regenerates the blinding factors must use the same `RngKind`.
+pub fn compute_commitments_with_rng_kind<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    rng_kind: RngKind,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    commit_rows(
+        data,
+        vector_committer,
+        blinding_factor_seed,
+        MAX_INPUT_LEN,
+        rng_kind,
+    )
+}
+
+fn commit_rows<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    max_len: usize,
+    rng_kind: RngKind,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    validate_input_len(data.len(), max_len)?;
+    let data_vec = pad_to_power_of_two(data);
 
     // calculate the number of blinding factors needed (=the number of rows in the matrix)
     let n_cols = vector_committer.generators.len();
     let n_rows = data_vec.len() / n_cols;
 
-    let mut prng = ChaCha20Rng::from_seed(blinding_factor_seed);
-    let blinding_factors = (0..n_rows)
+    let blinding_factors =
+        generate_blinding_factors_with_rng_kind::<C>(blinding_factor_seed, n_rows, rng_kind);
+
+    // we are using the vector_commit to commit to each of the rows of the matrix; the rows are
+    // independent, so under the `rayon` feature they are committed to in parallel (the blinding
+    // factors are drawn above, sequentially, so they do not depend on the scheduling)
+    #[cfg(feature = "rayon")]
+    let rows = data_vec.par_chunks(n_cols).zip(blinding_factors.par_iter());
+    #[cfg(not(feature = "rayon"))]
+    let rows = data_vec.chunks(n_cols).zip(blinding_factors.iter());
+    let commitment = rows
+        .map(|(chunk, blind)| vector_committer.vector_commit(chunk, blind))
+        .collect();
+
+    Ok(HyraxCommitmentOutput {
+        commitment,
+        blinding_factors,
+    })
+}
+
+/// Computes the same commitment as `compute_commitments` from data supplied incrementally, e.g.
+/// as it streams out of the camera pipeline, buffering a single row at a time instead of the
+/// whole padded input. Each row is committed to as soon as it is complete, with the next
+/// blinding factor from the same ChaCha20 stream as `compute_commitments`.
+pub struct HyraxCommitterStream<'a, C: PrimeOrderCurve> {
+    vector_committer: &'a PedersenCommitter<C>,
+    prng: ChaCha20Rng,
+    row_buffer: Vec<u8>,
+    len: usize,
+    commitment: Vec<C>,
+    blinding_factors: Vec<C::Scalar>,
+}
+
+impl<'a, C: PrimeOrderCurve> HyraxCommitterStream<'a, C> {
+    /// Starts a stream of data to be committed to under `vector_committer` and the seed.
+    /// Errors with `HyraxError::InvalidMatrixShape` if the committer has no generators, since
+    /// no data would ever fill a row.
+    pub fn new(
+        vector_committer: &'a PedersenCommitter<C>,
+        blinding_factor_seed: [u8; 32],
+    ) -> Result<Self, HyraxError> {
+        if vector_committer.generators.is_empty() {
+            return Err(HyraxError::InvalidMatrixShape {
+                data_len: 0,
+                n_cols: 0,
+            });
+        }
+        Ok(HyraxCommitterStream {
+            vector_committer,
+            prng: ChaCha20Rng::from_seed(blinding_factor_seed),
+            row_buffer: Vec::with_capacity(vector_committer.generators.len()),
+            len: 0,
+            commitment: vec![],
+            blinding_factors: vec![],
+        })
+    }
+
+    /// Appends `data` to the stream and returns the commitments to the rows it completed.
+    /// Errors with `HyraxError::InputTooLarge`, without consuming any of `data`, if the total
+    /// length would exceed `MAX_INPUT_LEN`.
+    pub fn update(&mut self, mut data: &[u8]) -> Result<&[C], HyraxError> {
+        let len = self.len.saturating_add(data.len());
+        validate_input_len(len, MAX_INPUT_LEN)?;
+        self.len = len;
+
+        let n_cols = self.vector_committer.generators.len();
+        let num_committed = self.commitment.len();
+        while !data.is_empty() {
+            let (head, rest) = data.split_at(data.len().min(n_cols - self.row_buffer.len()));
+            self.row_buffer.extend_from_slice(head);
+            data = rest;
+            if self.row_buffer.len() == n_cols {
+                self.commit_buffered_row();
+            }
+        }
+        Ok(&self.commitment[num_committed..])
+    }
+
+    /// Pads the data to the nearest power of 2 with 0s, exactly as `compute_commitments` does,
+    /// and returns the commitment to all of it.
+    /// Post: result == compute_commitments(all data, vector_committer, blinding_factor_seed)
+    pub fn finalize(mut self) -> HyraxCommitmentOutput<C> {
+        let n_cols = self.vector_committer.generators.len();
+        let n_rows = self.len.next_power_of_two() / n_cols;
+        while self.commitment.len() < n_rows {
+            self.row_buffer.resize(n_cols, 0);
+            self.commit_buffered_row();
+        }
+        HyraxCommitmentOutput {
+            commitment: self.commitment,
+            blinding_factors: self.blinding_factors,
+        }
+    }
+
+    // Commits to the (full) buffered row with the next blinding factor, and clears the buffer.
+    fn commit_buffered_row(&mut self) {
+        let blinding = C::Scalar::rand(&mut self.prng);
+        self.commitment.push(
+            self.vector_committer
+                .vector_commit(&self.row_buffer, &blinding),
+        );
+        self.blinding_factors.push(blinding);
+        self.row_buffer.clear();
+    }
+}
+
+/// Same as `compute_commitments`, for the `total_len` bytes yielded by `iter`, which are
+/// committed to row by row (see `HyraxCommitterStream`) without ever being materialized.
+/// Errors with `HyraxError::InputTooLarge` if total_len > MAX_INPUT_LEN, and with
+/// `HyraxError::InvalidByteLength` if `iter` does not yield exactly `total_len` bytes (if it
+/// yields more, the error reports `total_len + 1`, the first byte too many).
+pub fn compute_commitments_from_iter<C: PrimeOrderCurve>(
+    mut iter: impl Iterator<Item = u8>,
+    total_len: usize,
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    validate_input_len(total_len, MAX_INPUT_LEN)?;
+    let mut stream = HyraxCommitterStream::new(vector_committer, blinding_factor_seed)?;
+    let n_cols = vector_committer.generators.len();
+    let mut row = Vec::with_capacity(n_cols);
+    let mut len = 0;
+    loop {
+        row.clear();
+        row.extend(iter.by_ref().take(n_cols.min(total_len - len)));
+        if row.is_empty() {
+            break;
+        }
+        len += row.len();
+        stream.update(&row)?;
+    }
+    // stop reading at the first extra byte, so that an unbounded iterator is still rejected
+    let got = len + iter.next().map_or(0, |_| 1);
+    if got != total_len {
+        return Err(HyraxError::InvalidByteLength {
+            expected: total_len,
+            got,
+        });
+    }
+    Ok(stream.finalize())
+}
+
+/// Same as `compute_commitments`, but errors upfront with `HyraxError::MemoryBudgetExceeded` if
+/// the estimated working memory (see `commitment_memory_bytes`) exceeds `max_memory_bytes`. The
+/// rows are committed to one at a time with a `HyraxCommitterStream`, so that the padded data is
+/// never materialized and the estimate holds.
+pub fn compute_commitments_bounded<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+    max_memory_bytes: usize,
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    let required = commitment_memory_bytes(data.len(), vector_committer)?;
+    if required > max_memory_bytes {
+        return Err(HyraxError::MemoryBudgetExceeded {
+            required,
+            budget: max_memory_bytes,
+        });
+    }
+    let mut stream = HyraxCommitterStream::new(vector_committer, blinding_factor_seed)?;
+    stream.update(data)?;
+    Ok(stream.finalize())
+}
+
+/// Estimates the working memory of `compute_commitments_bounded` from the in-memory sizes of the
+/// curve points and scalars: the committer's generators and precomputed tables, a single row of
+/// the data, and the output's row commitments and blinding factors.
+/// Errors with `HyraxError::InvalidMatrixShape` if the committer has no generators.
+/// Pre: data_len <= MAX_INPUT_LEN
+pub fn commitment_memory_bytes<C: PrimeOrderCurve>(
+    data_len: usize,
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<usize, HyraxError> {
+    let n_cols = vector_committer.generators.len();
+    if n_cols == 0 {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: data_len.next_power_of_two(),
+            n_cols,
+        });
+    }
+    let n_rows = data_len.next_power_of_two() / n_cols;
+    let point_size = std::mem::size_of::<C>();
+    let scalar_size = std::mem::size_of::<C::Scalar>();
+    Ok(vector_committer.num_precomputed_points() * point_size
+        + n_cols
+        + n_rows * (point_size + scalar_size))
+}
+
+/// The ChaCha variant used to expand a blinding factor seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RngKind {
+    ChaCha8,
+    ChaCha12,
+    #[default]
+    ChaCha20,
+}
+
+/// Derives the blinding factors for an `n_rows`-row commitment from the seed, exactly as
+/// `compute_commitments` does: the first `n_rows` scalars drawn from a ChaCha20Rng seeded with
+/// `blinding_factor_seed`.
+pub fn generate_blinding_factors<C: PrimeOrderCurve>(
+    blinding_factor_seed: [u8; 32],
+    n_rows: usize,
+) -> Vec<C::Scalar> {
+    generate_blinding_factors_with_rng_kind::<C>(blinding_factor_seed, n_rows, RngKind::ChaCha20)
+}
+
+/// Same as `generate_blinding_factors`, drawing from the ChaCha variant selected by `rng_kind`.
+pub fn generate_blinding_factors_with_rng_kind<C: PrimeOrderCurve>(
+    blinding_factor_seed: [u8; 32],
+    n_rows: usize,
+    rng_kind: RngKind,
+) -> Vec<C::Scalar> {
+    let mut prng: Box<dyn RngCore> = match rng_kind {
+        RngKind::ChaCha8 => Box::new(ChaCha8Rng::from_seed(blinding_factor_seed)),
+        RngKind::ChaCha12 => Box::new(ChaCha12Rng::from_seed(blinding_factor_seed)),
+        RngKind::ChaCha20 => Box::new(ChaCha20Rng::from_seed(blinding_factor_seed)),
+    };
+    (0..n_rows)
         .map(|_idx| C::Scalar::rand(&mut prng))
+        .collect_vec()
+}
+
+/// How the blinding factors of a commitment were derived from its seed, which must be known to
+/// regenerate them (see `regenerate_blinding_factors`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlindingDerivation {
+    /// Drawn in row order from a single stream of the given ChaCha variant, as done by
+    /// `compute_commitments` (and `compute_commitments_binary_outputs`) with ChaCha20 and by
+    /// `compute_commitments_with_rng_kind`.
+    Sequential(RngKind),
+    /// Derived independently for each row with `derive_row_blindings`, as done by
+    /// `compute_commitments_parallel`.
+    PerRow,
+}
+
+impl Default for BlindingDerivation {
+    fn default() -> Self {
+        BlindingDerivation::Sequential(RngKind::default())
+    }
+}
+
+/// Regenerates the serialized blinding factors of an `n_rows`-row commitment from the same seed
+/// and `derivation` it was computed with, e.g. after restoring an escrowed seed from backup,
+/// without recomputing the commitment.
+/// Post: result == compute_commitments_binary_outputs(data, *seed)?.blinding_factors_serialized
+///     for any data with that number of rows, if derivation == BlindingDerivation::default().
+pub fn regenerate_blinding_factors(
+    seed: &BlindingSeed,
+    n_rows: usize,
+    derivation: BlindingDerivation,
+) -> Vec<u8> {
+    let blinding_factors = match derivation {
+        BlindingDerivation::Sequential(rng_kind) => {
+            generate_blinding_factors_with_rng_kind::<Bn256Point>(seed.bytes(), n_rows, rng_kind)
+        }
+        BlindingDerivation::PerRow => derive_row_blindings::<Bn256Point>(seed.bytes(), n_rows),
+    };
+    serialize_scalars::<Bn256Point>(&blinding_factors)
+}
+
+/// Checks that `len` bytes of data can be committed to under the bound `max_len`, returning
+/// the length the data is padded to.
+/// All arithmetic is checked, so no intermediate value can overflow, even on 32-bit targets.
+/// Post: result.is_power_of_two() && result >= len
+pub fn validate_input_len(len: usize, max_len: usize) -> Result<usize, HyraxError> {
+    let too_large = HyraxError::InputTooLarge { len, max: max_len };
+    if len > max_len {
+        return Err(too_large);
+    }
+    len.checked_next_power_of_two().ok_or(too_large)
+}
+
+/// Same as `compute_commitments`, committing to the rows in parallel (under the `rayon`
+/// feature). The blinding factors are derived with `derive_row_blindings` instead of being drawn
+/// from a single ChaCha20 stream, so they (and therefore the commitments) do not depend on how
+/// rows are scheduled across threads, but they differ from those of `compute_commitments` for
+/// the same seed.
+pub fn compute_commitments_parallel<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    let data_vec = pad_to_power_of_two(data);
+
+    let n_cols = vector_committer.generators.len();
+    let n_rows = data_vec.len() / n_cols;
+    let blinding_factors = derive_row_blindings::<C>(blinding_factor_seed, n_rows);
+
+    #[cfg(feature = "rayon")]
+    let rows = data_vec.par_chunks(n_cols).zip(blinding_factors.par_iter());
+    #[cfg(not(feature = "rayon"))]
+    let rows = data_vec.chunks(n_cols).zip(blinding_factors.iter());
+    let commitment = rows
+        .map(|(chunk, blind)| vector_committer.vector_commit(chunk, blind))
+        .collect();
+
+    Ok(HyraxCommitmentOutput {
+        commitment,
+        blinding_factors,
+    })
+}
+
+/// Derives the blinding factor of each of `n_rows` rows independently, see `derive_row_blinding`.
+/// Post: result[i] == derive_row_blinding(blinding_factor_seed, i)
+pub fn derive_row_blindings<C: PrimeOrderCurve>(
+    blinding_factor_seed: [u8; 32],
+    n_rows: usize,
+) -> Vec<C::Scalar> {
+    #[cfg(feature = "rayon")]
+    let row_indices = (0..n_rows).into_par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let row_indices = 0..n_rows;
+    row_indices
+        .map(|row_index| derive_row_blinding::<C>(blinding_factor_seed, row_index))
+        .collect()
+}
+
+/// Derives the blinding factor of row `row_index` from ChaCha20 stream number `row_index` under
+/// `blinding_factor_seed`, reducing its first 64 bytes modulo the scalar field order (so that the
+/// result is statistically close to uniform).
+pub fn derive_row_blinding<C: PrimeOrderCurve>(
+    blinding_factor_seed: [u8; 32],
+    row_index: usize,
+) -> C::Scalar {
+    let mut prng = ChaCha20Rng::from_seed(blinding_factor_seed);
+    prng.set_stream(row_index as u64);
+    let mut bytes = [0u8; 64];
+    prng.fill_bytes(&mut bytes);
+    C::Scalar::from_le_bytes_mod_order(&bytes)
+}
+
+/// Domain separator for the blinding factors re-derived by `reseed_trivial_rows`.
+const RESEED_DOMAIN: &[u8] = b"hyrax row blinding reseed";
+
+/// The output of `compute_commitments_safe`: the row commitments, none of which is the identity,
+/// plus the rows whose blinding factor was re-derived, each with the counter it was re-derived
+/// from (see `reseeded_row_blinding`), so that the blinding factors can be reproduced.
+pub struct SafeCommitmentOutput<C: PrimeOrderCurve> {
+    pub output: HyraxCommitmentOutput<C>,
+    pub reseeded_rows: Vec<(usize, u64)>,
+}
+
+/// Same as `compute_commitments`, but guarantees that no row commitment is the identity, which
+/// would reveal that the row is all zeros with a zero blinding factor. Such rows are fixed up
+/// with `reseed_trivial_rows`; all other rows, and their blinding factors, are exactly those of
+/// `compute_commitments`.
+pub fn compute_commitments_safe<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<SafeCommitmentOutput<C>, HyraxError> {
+    let output = compute_commitments(data, vector_committer, blinding_factor_seed)?;
+    reseed_trivial_rows(data, output, vector_committer, blinding_factor_seed)
+}
+
+/// Re-commits each row of `output` whose commitment is the identity, with the blinding factor
+/// `reseeded_row_blinding(blinding_factor_seed, row, counter)` for the first counter (from 1 up)
+/// that gives a non-identity commitment, and records the counter.
+/// Pre: `output` is a commitment to `data` under `vector_committer`.
+/// Errors if `data` does not split into rows of `vector_committer`'s length, or if `output` does
+/// not have exactly one commitment and one blinding factor per row.
+pub fn reseed_trivial_rows<C: PrimeOrderCurve>(
+    data: &[u8],
+    mut output: HyraxCommitmentOutput<C>,
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<SafeCommitmentOutput<C>, HyraxError> {
+    let n_cols = vector_committer.generators.len();
+    let data_vec = padded_opening_data(data, n_cols, &output.commitment, &output.blinding_factors)?;
+    let mut reseeded_rows = vec![];
+    for (row_index, row) in data_vec.chunks(n_cols).enumerate() {
+        if output.commitment[row_index] != C::zero() {
+            continue;
+        }
+        let mut counter = 0;
+        while output.commitment[row_index] == C::zero() {
+            counter += 1;
+            let blinding = reseeded_row_blinding::<C>(blinding_factor_seed, row_index, counter);
+            output.blinding_factors[row_index] = blinding;
+            output.commitment[row_index] = vector_committer.vector_commit(row, &blinding);
+        }
+        reseeded_rows.push((row_index, counter));
+    }
+    Ok(SafeCommitmentOutput {
+        output,
+        reseeded_rows,
+    })
+}
+
+/// Derives the re-seeded blinding factor of row `row_index` for retry `counter`: the first 64
+/// bytes of a ChaCha20Rng seeded with the BLAKE2s-256 digest of a domain separator, the seed,
+/// the row index and the counter, reduced modulo the scalar field order.
+pub fn reseeded_row_blinding<C: PrimeOrderCurve>(
+    blinding_factor_seed: [u8; 32],
+    row_index: usize,
+    counter: u64,
+) -> C::Scalar {
+    let digest: [u8; 32] = Blake2s256::new()
+        .chain_update(RESEED_DOMAIN)
+        .chain_update(blinding_factor_seed)
+        .chain_update((row_index as u64).to_le_bytes())
+        .chain_update(counter.to_le_bytes())
+        .finalize()
+        .into();
+    let mut prng = ChaCha20Rng::from_seed(digest);
+    let mut bytes = [0u8; 64];
+    prng.fill_bytes(&mut bytes);
+    C::Scalar::from_le_bytes_mod_order(&bytes)
+}
+
+/// The output of `compute_commitments_with_blinding_commitment`: the row commitments, plus a
+/// Pedersen commitment to the vector of their blinding factors and the blinding factor of that
+/// commitment (which, like the row blinding factors, must stay with the prover).
+pub struct BlindingCommittedOutput<C: PrimeOrderCurve> {
+    pub output: HyraxCommitmentOutput<C>,
+    pub blinding_commitment: C,
+    pub blinding_commitment_blinding_factor: C::Scalar,
+}
+
+/// Same as `compute_commitments`, additionally committing to the vector of row blinding factors.
+/// The blinding factors are committed to as scalars under generators derived from
+/// `BLINDING_COMMITMENT_PUBLIC_STRING` (see `blinding_commitment_committer`), with the next
+/// scalar of the ChaCha20 stream after the row blinding factors as their blinding factor, so the
+/// row commitments and blinding factors are identical to those of `compute_commitments`.
+pub fn compute_commitments_with_blinding_commitment<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<BlindingCommittedOutput<C>, HyraxError> {
+    let output = compute_commitments(data, vector_committer, blinding_factor_seed)?;
+    let n_rows = output.commitment.len();
+    let blinding_commitment_blinding_factor =
+        generate_blinding_factors::<C>(blinding_factor_seed, n_rows + 1)[n_rows];
+
+    let blinding_committer = blinding_commitment_committer::<C>(n_rows);
+    let blinding_commitment = C::msm(&output.blinding_factors, &blinding_committer.generators)
+        + blinding_committer.blinding_generator * blinding_commitment_blinding_factor;
+
+    Ok(BlindingCommittedOutput {
+        output,
+        blinding_commitment,
+        blinding_commitment_blinding_factor,
+    })
+}
+
+/// The committer whose generators are used to commit to the blinding factors of an
+/// `n_rows`-row commitment in `compute_commitments_with_blinding_commitment`.
+pub fn blinding_commitment_committer<C: PrimeOrderCurve>(n_rows: usize) -> PedersenCommitter<C> {
+    PedersenCommitter::new(n_rows, BLINDING_COMMITMENT_PUBLIC_STRING)
+}
+
+/// Commits to each full window `data[i * stride..i * stride + window]` as one row, so that
+/// consecutive windows overlap whenever `stride < window`. Data past the last full window is not
+/// committed to, and no padding is applied.
+/// Blinding factors are the first ones drawn from the ChaCha20Rng seeded with
+/// `blinding_factor_seed`, one per window, in window order.
+/// Errors with `HyraxError::InvalidWindow` unless 0 < stride and window <= generators.len().
+/// Post: result.commitment.len() == (data.len() - window) / stride + 1 if data.len() >= window
+pub fn commit_sliding_windows<C: PrimeOrderCurve>(
+    data: &[u8],
+    window: usize,
+    stride: usize,
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    let n_cols = vector_committer.generators.len();
+    if stride == 0 || window > n_cols {
+        return Err(HyraxError::InvalidWindow {
+            window,
+            stride,
+            n_cols,
+        });
+    }
+    validate_input_len(data.len(), MAX_INPUT_LEN)?;
+
+    let n_windows = if data.len() >= window {
+        (data.len() - window) / stride + 1
+    } else {
+        0
+    };
+    let blinding_factors = generate_blinding_factors::<C>(blinding_factor_seed, n_windows);
+    let commitment = (0..n_windows)
+        .zip(blinding_factors.iter())
+        .map(|(idx, blind)| {
+            let start = idx * stride;
+            vector_committer.vector_commit(&data[start..start + window], blind)
+        })
         .collect_vec();
 
-    // we are using the vector_commit to commit to each of the rows of the matrix
-    let row_chunks = data_vec.chunks(n_cols);
-    let commitment = row_chunks
+    Ok(HyraxCommitmentOutput {
+        commitment,
+        blinding_factors,
+    })
+}
+
+/// Commits to the deviation `data[i] - baseline[i]` (as a signed value) of the data from a
+/// public baseline, laid out and blinded exactly as `compute_commitments` lays out and blinds
+/// `data`. Adding the unblinded row commitments to the baseline therefore recovers the
+/// commitment to `data`.
+/// Errors with `HyraxError::BaselineLengthMismatch` if data and baseline differ in length.
+pub fn commit_delta_from_baseline<C: PrimeOrderCurve>(
+    data: &[u8],
+    baseline: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    if data.len() != baseline.len() {
+        return Err(HyraxError::BaselineLengthMismatch {
+            data_len: data.len(),
+            baseline_len: baseline.len(),
+        });
+    }
+    let padded_len = validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    let mut deltas = data
+        .iter()
+        .zip(baseline.iter())
+        .map(|(element, baseline_element)| *element as i16 - *baseline_element as i16)
+        .collect_vec();
+    deltas.resize(padded_len, 0);
+
+    let n_cols = vector_committer.generators.len();
+    let n_rows = padded_len / n_cols;
+    let blinding_factors = generate_blinding_factors::<C>(blinding_factor_seed, n_rows);
+    let commitment = deltas
+        .chunks(n_cols)
+        .zip(blinding_factors.iter())
+        .map(|(chunk, blind)| vector_committer.signed_vector_commit(chunk, blind))
+        .collect_vec();
+
+    Ok(HyraxCommitmentOutput {
+        commitment,
+        blinding_factors,
+    })
+}
+
+/// The output of `compute_commitments_salted`: the commitment to `salt || data`, plus the
+/// length of the salt, i.e. the number of leading committed elements that an opening of the
+/// data should skip.
+pub struct SaltedCommitmentOutput<C: PrimeOrderCurve> {
+    pub output: HyraxCommitmentOutput<C>,
+    pub salt_len: usize,
+}
+
+/// Compute the commitments to `salt || data`, binding the commitment to e.g. a session nonce:
+/// the same data under different salts yields unrelatable commitments.
+/// The salted data is padded with 0s to the nearest power of 2, exactly as in `compute_commitments`.
+pub fn compute_commitments_salted<C: PrimeOrderCurve>(
+    data: &[u8],
+    salt: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<SaltedCommitmentOutput<C>, HyraxError> {
+    let salted_data = salt.iter().chain(data.iter()).copied().collect_vec();
+    let output = compute_commitments(&salted_data, vector_committer, blinding_factor_seed)?;
+    Ok(SaltedCommitmentOutput {
+        output,
+        salt_len: salt.len(),
+    })
+}
+
+/// The output of `compute_commitments_unpadded`: the row commitments and blinding factors,
+/// plus the number of data elements in the final (possibly short) row.
+pub struct UnpaddedCommitmentOutput<C: PrimeOrderCurve> {
+    pub output: HyraxCommitmentOutput<C>,
+    pub last_row_len: usize,
+}
+
+/// Compute the commitments to the data without padding it.
+/// Unlike `compute_commitments`, the data is not padded to a power of two: the matrix has
+/// ceil(data.len() / n_cols) rows, and the final row is committed to only its `last_row_len`
+/// elements, against the first `last_row_len` generators.
+/// The short row's commitment is the same group element as its zero-padded commitment would be
+/// (a zero entry contributes nothing); the difference is that no all-zero padding rows are added,
+/// and `last_row_len` records where the data ends, so that the committed polynomial is the MLE
+/// of the unpadded data rather than of the data followed by zeros.
+/// Blinding factors are generated exactly as in `compute_commitments`.
+/// Errors with `HyraxError::InputTooLarge` if data.len() > MAX_INPUT_LEN, with
+/// `HyraxError::EmptyInput` if the data is empty, and with `HyraxError::InvalidMatrixShape` if
+/// the committer has no generators.
+/// Post: result.output.commitment.len() == data.len().div_ceil(vector_committer.generators.len())
+pub fn compute_commitments_unpadded<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<UnpaddedCommitmentOutput<C>, HyraxError> {
+    validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    if data.is_empty() {
+        return Err(HyraxError::EmptyInput);
+    }
+    let n_cols = vector_committer.generators.len();
+    if n_cols == 0 {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: data.len(),
+            n_cols,
+        });
+    }
+    let n_rows = data.len().div_ceil(n_cols);
+
+    let blinding_factors = generate_blinding_factors::<C>(blinding_factor_seed, n_rows);
+
+    let commitment = data
+        .chunks(n_cols)
         .zip(blinding_factors.iter())
         .map(|(chunk, blind)| vector_committer.vector_commit(chunk, blind))
         .collect_vec();
 
-    HyraxCommitmentOutput {
+    Ok(UnpaddedCommitmentOutput {
+        output: HyraxCommitmentOutput {
+            commitment,
+            blinding_factors,
+        },
+        last_row_len: data.len() - (n_rows - 1) * n_cols,
+    })
+}
+
+/// Number of bit-planes in a u8 image.
+pub const NUM_BIT_PLANES: usize = 8;
+
+/// Commit separately to each of the 8 bit-planes of the data, least significant plane first.
+/// Each plane is the boolean vector of the k-th bits of the (padded) data, committed
+/// row-by-row with `PedersenCommitter::bit_vector_commit`.
+/// Blinding factors for all planes are drawn with `generate_blinding_factors`, as a single
+/// stream: the n_rows factors of plane 0, then those of plane 1, etc.
+/// Errors with `HyraxError::InputTooLarge` if data.len() > MAX_INPUT_LEN.
+/// Post: result.len() == NUM_BIT_PLANES
+pub fn commit_bit_planes<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<Vec<HyraxCommitmentOutput<C>>, HyraxError> {
+    validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    let data_vec = pad_to_power_of_two(data);
+    let n_cols = vector_committer.generators.len();
+    let n_rows = data_vec.len() / n_cols;
+
+    let all_blinding_factors =
+        generate_blinding_factors::<C>(blinding_factor_seed, NUM_BIT_PLANES * n_rows);
+    Ok((0..NUM_BIT_PLANES)
+        .map(|plane| {
+            let blinding_factors =
+                all_blinding_factors[plane * n_rows..(plane + 1) * n_rows].to_vec();
+            let commitment = data_vec
+                .chunks(n_cols)
+                .zip(blinding_factors.iter())
+                .map(|(chunk, blind)| {
+                    let bits = chunk
+                        .iter()
+                        .map(|value| (value >> plane) & 1 == 1)
+                        .collect_vec();
+                    vector_committer.bit_vector_commit(&bits, blind)
+                })
+                .collect_vec();
+            HyraxCommitmentOutput {
+                commitment,
+                blinding_factors,
+            }
+        })
+        .collect())
+}
+
+/// Compute the commitments to run-length-encoded data, where each `(value, run_len)` stands for
+/// `run_len` consecutive copies of `value`. The runs are committed directly, without
+/// materializing the expanded data.
+/// The expanded length is bounded by `MAX_INPUT_LEN` and padded with 0s to the nearest power of 2,
+/// as in `compute_commitments`, and must then fill a whole number of rows.
+/// Post: same result as `compute_commitments` on the expanded data with the same seed.
+pub fn commit_rle<C: PrimeOrderCurve>(
+    runs: &[(u8, usize)],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<HyraxCommitmentOutput<C>, HyraxError> {
+    let n_cols = vector_committer.generators.len();
+    let total_len = runs
+        .iter()
+        .try_fold(0_usize, |acc, (_, run_len)| acc.checked_add(*run_len))
+        .ok_or(HyraxError::InputTooLarge {
+            len: usize::MAX,
+            max: MAX_INPUT_LEN,
+        })?;
+    let padded_len = validate_input_len(total_len, MAX_INPUT_LEN)?;
+    if padded_len < n_cols || padded_len % n_cols != 0 {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: total_len,
+            n_cols,
+        });
+    }
+    let n_rows = padded_len / n_cols;
+
+    let blinding_factors = generate_blinding_factors::<C>(blinding_factor_seed, n_rows);
+
+    // --- Split the runs at row boundaries; the zero padding contributes nothing ---
+    let mut row_runs: Vec<Vec<(u8, usize)>> = vec![vec![]; n_rows];
+    let mut position = 0;
+    for (value, run_len) in runs {
+        let mut remaining = *run_len;
+        while remaining > 0 {
+            let row_idx = position / n_cols;
+            let taken = remaining.min(n_cols - position % n_cols);
+            row_runs[row_idx].push((*value, taken));
+            position += taken;
+            remaining -= taken;
+        }
+    }
+
+    let commitment = row_runs
+        .iter()
+        .zip(blinding_factors.iter())
+        .map(|(runs, blind)| vector_committer.try_run_length_vector_commit(runs, blind))
+        .collect::<Result<Vec<C>, HyraxError>>()?;
+
+    Ok(HyraxCommitmentOutput {
         commitment,
         blinding_factors,
+    })
+}
+
+/// One of the two channels of a joint iris code/mask commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JointChannel {
+    Iris,
+    Mask,
+}
+
+/// The layout of a joint commitment: the iris code and the mask, each of `channel_len` elements,
+/// are interleaved element by element (`iris[0], mask[0], iris[1], mask[1], ...`) into a single
+/// vector, which is committed to as in `compute_commitments` with `1 << log_num_cols` columns.
+/// The mask bits thus occupy the odd columns, i.e. a dedicated half of the generators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JointCommitmentLayout {
+    pub channel_len: usize,
+    pub log_num_cols: usize,
+}
+
+impl JointCommitmentLayout {
+    /// Returns the number of variables of the multilinear extension of each (padded) channel.
+    pub fn num_channel_vars(&self) -> usize {
+        self.channel_len.next_power_of_two().ilog2() as usize
+    }
+
+    /// Returns the evaluation point of the joint commitment (as in `prove_evaluation`: row
+    /// coordinates, then column coordinates) at which its multilinear extension equals that of
+    /// `channel` at `channel_point`. `channel_point` has one coordinate per bit of the element
+    /// index within the channel, least significant first.
+    /// Since joint element `2 * i + c` is element `i` of channel `c`, the joint point is the
+    /// channel point preceded by the channel selector bit.
+    /// Errors with `HyraxError::InvalidJointLayout` if the joint data does not fill a single row,
+    /// and with `HyraxError::InvalidEvaluationPointLength` if `channel_point` has the wrong length.
+    pub fn joint_point<F: PrimeField>(
+        &self,
+        channel: JointChannel,
+        channel_point: &[F],
+    ) -> Result<Vec<F>, HyraxError> {
+        let num_joint_vars = self.num_channel_vars() + 1;
+        if num_joint_vars < self.log_num_cols {
+            return Err(HyraxError::InvalidJointLayout {
+                log_num_cols: self.log_num_cols,
+                num_joint_vars,
+            });
+        }
+        if channel_point.len() != self.num_channel_vars() {
+            return Err(HyraxError::InvalidEvaluationPointLength {
+                point_len: channel_point.len(),
+                expected: self.num_channel_vars(),
+            });
+        }
+        let selector = match channel {
+            JointChannel::Iris => F::zero(),
+            JointChannel::Mask => F::one(),
+        };
+        // --- The joint index bits, least significant first ---
+        let joint_vars = std::iter::once(selector)
+            .chain(channel_point.iter().copied())
+            .collect_vec();
+        let (col_vars, row_vars) = joint_vars.split_at(self.log_num_cols);
+        Ok(row_vars.iter().chain(col_vars.iter()).copied().collect())
+    }
+
+    /// Extracts one channel from (the unpadded prefix of) interleaved joint data.
+    /// Mask elements are returned as 0 or 1.
+    /// Pre: joint_data.len() >= 2 * self.channel_len
+    pub fn extract_channel(&self, joint_data: &[u8], channel: JointChannel) -> Vec<u8> {
+        let offset = match channel {
+            JointChannel::Iris => 0,
+            JointChannel::Mask => 1,
+        };
+        joint_data[..2 * self.channel_len]
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .copied()
+            .collect()
+    }
+}
+
+/// The output of `commit_joint`: the commitment to the interleaved iris code and mask, along with
+/// the layout needed to open either channel.
+pub struct JointCommitmentOutput<C: PrimeOrderCurve> {
+    pub output: HyraxCommitmentOutput<C>,
+    pub layout: JointCommitmentLayout,
+}
+
+/// Interleaves the iris code with the mask element by element, encoding each mask bit as a 0 or
+/// 1 element, as laid out by `JointCommitmentLayout`.
+/// Errors with `HyraxError::ChannelLengthMismatch` if the channels have different lengths.
+pub fn interleave_joint(iris: &[u8], mask: &[bool]) -> Result<Vec<u8>, HyraxError> {
+    if iris.len() != mask.len() {
+        return Err(HyraxError::ChannelLengthMismatch {
+            iris_len: iris.len(),
+            mask_len: mask.len(),
+        });
+    }
+    Ok(iris
+        .iter()
+        .zip(mask.iter())
+        .flat_map(|(iris_element, mask_bit)| [*iris_element, *mask_bit as u8])
+        .collect())
+}
+
+/// Compute a single commitment binding both the iris code and its mask, so that relations
+/// between the two can be proven against one commitment. The channels are interleaved with
+/// `interleave_joint`, and the result is committed to as in `compute_commitments`; either
+/// channel is opened with `prove_evaluation` on the interleaved data at
+/// `JointCommitmentLayout::joint_point`.
+pub fn commit_joint<C: PrimeOrderCurve>(
+    iris: &[u8],
+    mask: &[bool],
+    vector_committer: &PedersenCommitter<C>,
+    blinding_factor_seed: [u8; 32],
+) -> Result<JointCommitmentOutput<C>, HyraxError> {
+    let joint_data = interleave_joint(iris, mask)?;
+    let output = compute_commitments(&joint_data, vector_committer, blinding_factor_seed)?;
+    Ok(JointCommitmentOutput {
+        output,
+        layout: JointCommitmentLayout {
+            channel_len: iris.len(),
+            log_num_cols: vector_committer.generators.len().ilog2() as usize,
+        },
+    })
+}
+
+// pad the data to the nearest power of 2 by appending 0s
+pub(crate) fn pad_to_power_of_two(data: &[u8]) -> Vec<u8> {
+    let nearest_power_of_2_len = data.len().next_power_of_two();
+    let mut data_vec = data.to_vec();
+    data_vec.resize(nearest_power_of_2_len, 0);
+    data_vec
+}
+
+/// A Hyrax evaluation proof for a commitment to a matrix `M` of `n_rows` rows: the combination of
+/// the rows `L^T M` by the row half `L` of the tensor-expanded evaluation point, along with the
+/// same combination of the row blinding factors.
+/// Note that this reveals `L^T M`, so it is not zero-knowledge on its own; it is the opening
+/// that a zero-knowledge dot-product argument would otherwise prove knowledge of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpeningProof<C: PrimeOrderCurve> {
+    pub row_combination: Vec<C::Scalar>,
+    pub combined_blinding_factor: C::Scalar,
+}
+
+/// Returns the evaluations of the multilinear Lagrange basis at `point`, i.e. the vector whose
+/// entry `i` is `prod_j (point[j] if bit j of i is set else 1 - point[j])`, with bit 0 the least
+/// significant.
+/// Post: result.len() == 1 << point.len()
+pub fn tensor_expand<F: PrimeField>(point: &[F]) -> Vec<F> {
+    let mut evals = vec![F::one()];
+    for coordinate in point {
+        let len = evals.len();
+        evals.extend_from_within(..);
+        for i in 0..len {
+            evals[i + len] = evals[i] * coordinate;
+            evals[i] *= F::one() - coordinate;
+        }
+    }
+    evals
+}
+
+/// Proves the evaluation of the multilinear extension of the committed data at `point`.
+/// The data is laid out as in `compute_commitments` (padded to a power of two, one row per
+/// `vector_committer.generators.len()` elements), and `point` consists of the `log2(n_rows)`
+/// row coordinates followed by the `log2(n_cols)` column coordinates, so that the evaluation is
+/// `sum_{row, col} M[row][col] * tensor_expand(row_point)[row] * tensor_expand(col_point)[col]`.
+/// Errors if the data and blinding factors do not match a commitment under `vector_committer`,
+/// or if the point has the wrong number of coordinates.
+pub fn prove_evaluation<C: PrimeOrderCurve>(
+    data: &[u8],
+    blinding_factors: &[C::Scalar],
+    point: &[C::Scalar],
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<OpeningProof<C>, HyraxError> {
+    let scalar_data = data
+        .iter()
+        .map(|element| C::Scalar::from(*element as u64))
+        .collect_vec();
+    prove_evaluation_for_scalars(&scalar_data, blinding_factors, point, vector_committer)
+}
+
+/// Same as `prove_evaluation`, for data given by its scalar field interpretation. This opens any
+/// commitment to the rows, however they were committed to: e.g. for rows committed to with
+/// `signed_vector_commit`, negative elements are passed as their negations in the scalar field.
+/// As in `prove_evaluation`, the data is padded with zeros to a power of two.
+pub fn prove_evaluation_for_scalars<C: PrimeOrderCurve>(
+    data: &[C::Scalar],
+    blinding_factors: &[C::Scalar],
+    point: &[C::Scalar],
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<OpeningProof<C>, HyraxError> {
+    let mut data_vec = data.to_vec();
+    data_vec.resize(data.len().next_power_of_two(), C::Scalar::zero());
+    let n_cols = vector_committer.generators.len();
+    if !n_cols.is_power_of_two() || data_vec.len() % n_cols != 0 {
+        return Err(HyraxError::InvalidMatrixShape {
+            data_len: data_vec.len(),
+            n_cols,
+        });
+    }
+    let n_rows = data_vec.len() / n_cols;
+    if blinding_factors.len() != n_rows {
+        return Err(HyraxError::BlindingFactorCountMismatch {
+            expected: n_rows,
+            actual: blinding_factors.len(),
+        });
+    }
+    let log_num_cols = n_cols.ilog2() as usize;
+    validate_point_len(point.len(), n_rows, log_num_cols)?;
+
+    let row_weights = tensor_expand(&point[..point.len() - log_num_cols]);
+    let mut row_combination = vec![C::Scalar::zero(); n_cols];
+    for (row, row_weight) in data_vec.chunks(n_cols).zip(row_weights.iter()) {
+        for (acc, element) in row_combination.iter_mut().zip(row.iter()) {
+            *acc += *row_weight * element;
+        }
     }
+    let combined_blinding_factor = combine_blinding_factors(blinding_factors, &row_weights);
+
+    Ok(OpeningProof {
+        row_combination,
+        combined_blinding_factor,
+    })
+}
+
+/// Verifies that the data committed to by `commitment` (under `vector_committer`) evaluates to
+/// `claimed_evaluation` at `point` (see `prove_evaluation` for the layout), by checking that
+/// * the proof's row combination, committed to with the combined blinding factor, equals the
+///     same combination `sum_i L_i * commitment[i]` of the row commitments, and
+/// * its inner product with the column half of the tensor-expanded point is the claimed evaluation.
+pub fn verify_evaluation<C: PrimeOrderCurve>(
+    commitment: &[C],
+    point: &[C::Scalar],
+    claimed_evaluation: C::Scalar,
+    proof: &OpeningProof<C>,
+    vector_committer: &PedersenCommitter<C>,
+) -> bool {
+    let n_cols = vector_committer.generators.len();
+    if !n_cols.is_power_of_two() || proof.row_combination.len() != n_cols {
+        return false;
+    }
+    let log_num_cols = n_cols.ilog2() as usize;
+    if validate_point_for_commitment(point, commitment, log_num_cols).is_err() {
+        return false;
+    }
+    let (row_point, col_point) = point.split_at(point.len() - log_num_cols);
+
+    let opened_commitment = C::msm(&proof.row_combination, &vector_committer.generators)
+        + vector_committer.blinding_generator * proof.combined_blinding_factor;
+
+    combine_row_commitments(commitment, row_point) == opened_commitment
+        && proof.evaluation(col_point) == claimed_evaluation
+}
+
+/// Returns the combination `sum_i L_i * commitment[i]` of the row commitments by the
+/// tensor-expanded row half `L` of an evaluation point, i.e. a commitment to the proof's row
+/// combination under the combined blinding factor.
+/// Pre: commitment.len() == 1 << row_point.len()
+pub fn combine_row_commitments<C: PrimeOrderCurve>(commitment: &[C], row_point: &[C::Scalar]) -> C {
+    assert_eq!(commitment.len(), 1 << row_point.len());
+    combine_commitments(commitment, &tensor_expand(row_point))
+}
+
+/// Returns the linear combination `sum_i coeffs[i] * commitments[i]` of the commitments. By the
+/// additive homomorphism of Pedersen commitments, this is the commitment to the same combination
+/// of the committed rows, under the blinding factor `combine_blinding_factors(blinding_factors,
+/// coeffs)`.
+/// Pre: commitments.len() == coeffs.len()
+pub fn combine_commitments<C: PrimeOrderCurve>(commitments: &[C], coeffs: &[C::Scalar]) -> C {
+    assert_eq!(commitments.len(), coeffs.len());
+    C::msm(coeffs, commitments)
+}
+
+/// Returns the linear combination `sum_i coeffs[i] * blinding_factors[i]` of the blinding
+/// factors, i.e. the blinding factor of `combine_commitments(commitments, coeffs)`.
+/// Pre: blinding_factors.len() == coeffs.len()
+pub fn combine_blinding_factors<F: PrimeField>(blinding_factors: &[F], coeffs: &[F]) -> F {
+    assert_eq!(blinding_factors.len(), coeffs.len());
+    blinding_factors
+        .iter()
+        .zip(coeffs.iter())
+        .fold(F::zero(), |acc, (blind, coeff)| acc + *blind * coeff)
+}
+
+impl<C: PrimeOrderCurve> OpeningProof<C> {
+    /// Returns the evaluation this proof opens to at the column half `col_point` of the
+    /// evaluation point: the inner product of the row combination with `tensor_expand(col_point)`.
+    /// Pre: self.row_combination.len() == 1 << col_point.len()
+    pub fn evaluation(&self, col_point: &[C::Scalar]) -> C::Scalar {
+        assert_eq!(self.row_combination.len(), 1 << col_point.len());
+        self.row_combination
+            .iter()
+            .zip(tensor_expand(col_point).iter())
+            .fold(C::Scalar::zero(), |acc, (element, col_weight)| {
+                acc + *element * col_weight
+            })
+    }
+}
+
+/// The version of the `OpeningProof::to_bytes` format, its first byte.
+pub const OPENING_PROOF_VERSION: u8 = 1;
+
+/// The byte length of the `OpeningProof::to_bytes` header: the version, then `log2` of the number
+/// of columns.
+pub const OPENING_PROOF_HEADER_LEN: usize = 2;
+
+impl<C: PrimeOrderCurve> OpeningProof<C> {
+    /// Serializes the proof as the header (`OPENING_PROOF_VERSION`, then `log2` of the length of
+    /// the row combination), followed by the `serialize_scalars` encoding of the row combination
+    /// and of the combined blinding factor. This is the format sent from prover to verifier.
+    /// Pre: self.row_combination.len() is a power of two
+    /// Post: result.len() == opening_proof_byte_len::<C>(log2(self.row_combination.len()))
+    pub fn to_bytes(&self) -> Vec<u8> {
+        assert!(self.row_combination.len().is_power_of_two());
+        let log_num_cols = self.row_combination.len().ilog2() as u8;
+        let mut bytes = vec![OPENING_PROOF_VERSION, log_num_cols];
+        bytes.extend(serialize_scalars::<C>(&self.row_combination));
+        bytes.extend(serialize_scalars::<C>(&[self.combined_blinding_factor]));
+        bytes
+    }
+
+    /// Inverse of `to_bytes`.
+    /// Errors with `HyraxError::DeserializationError` on an unknown version, with
+    /// `HyraxError::InvalidByteLength` if the length does not match the header, and with
+    /// `HyraxError::NonCanonicalCoordinate` if a scalar is not reduced modulo the field order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HyraxError> {
+        if bytes.len() < OPENING_PROOF_HEADER_LEN {
+            return Err(HyraxError::InvalidByteLength {
+                expected: OPENING_PROOF_HEADER_LEN,
+                got: bytes.len(),
+            });
+        }
+        if bytes[0] != OPENING_PROOF_VERSION {
+            return Err(HyraxError::DeserializationError(format!(
+                "unsupported opening proof version {}",
+                bytes[0]
+            )));
+        }
+        let log_num_cols = bytes[1] as usize;
+        let expected =
+            opening_proof_byte_len::<C>(log_num_cols).ok_or(HyraxError::DeserializationError(
+                format!("opening proof of 2^{log_num_cols} columns is too large"),
+            ))?;
+        if bytes.len() != expected {
+            return Err(HyraxError::InvalidByteLength {
+                expected,
+                got: bytes.len(),
+            });
+        }
+        let mut scalars = bytes[OPENING_PROOF_HEADER_LEN..]
+            .chunks(C::SCALAR_ELEM_BYTEWIDTH)
+            .map(canonical_from_le_bytes::<C::Scalar>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let combined_blinding_factor = scalars.pop().unwrap();
+        Ok(OpeningProof {
+            row_combination: scalars,
+            combined_blinding_factor,
+        })
+    }
+}
+
+/// Returns the byte length of a serialized `OpeningProof` (see `OpeningProof::to_bytes`) for a
+/// matrix of `1 << log_num_cols` columns: the header, then one scalar per column and the combined
+/// blinding factor. The length does not depend on the number of rows.
+pub fn opening_proof_byte_len<C: PrimeOrderCurve>(log_num_cols: usize) -> Option<usize> {
+    1_usize
+        .checked_shl(log_num_cols.try_into().ok()?)?
+        .checked_add(1)?
+        .checked_mul(C::SCALAR_ELEM_BYTEWIDTH)?
+        .checked_add(OPENING_PROOF_HEADER_LEN)
+}
+
+/// Cheaply checks, before parsing it, that an untrusted serialized opening proof has exactly the
+/// size an opening of a `commitment_len`-row commitment with `1 << log_num_cols` columns has
+/// (see `opening_proof_byte_len`), so that oversized inputs are rejected before any allocation.
+/// Errors with `HyraxError::NonPowerOfTwoRows` if `commitment_len` is not a power of two, with
+/// `HyraxError::InputTooLarge` if the committed matrix would exceed `MAX_INPUT_LEN` elements,
+/// and with `HyraxError::InvalidByteLength` if the proof has the wrong length.
+pub fn validate_proof_size<C: PrimeOrderCurve>(
+    proof_bytes: &[u8],
+    commitment_len: usize,
+    log_num_cols: usize,
+) -> Result<(), HyraxError> {
+    if !commitment_len.is_power_of_two() {
+        return Err(HyraxError::NonPowerOfTwoRows(commitment_len));
+    }
+    let too_large = HyraxError::InputTooLarge {
+        len: usize::MAX,
+        max: MAX_INPUT_LEN,
+    };
+    let num_elements = u32::try_from(log_num_cols)
+        .ok()
+        .and_then(|shift| commitment_len.checked_shl(shift))
+        .filter(|num_elements| num_elements >> log_num_cols == commitment_len)
+        .ok_or(too_large.clone())?;
+    if num_elements > MAX_INPUT_LEN {
+        return Err(HyraxError::InputTooLarge {
+            len: num_elements,
+            max: MAX_INPUT_LEN,
+        });
+    }
+    let expected = opening_proof_byte_len::<C>(log_num_cols).ok_or(too_large)?;
+    if proof_bytes.len() != expected {
+        return Err(HyraxError::InvalidByteLength {
+            expected,
+            got: proof_bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that `commitment` opens to `data` under `blinding_factors`, by recomputing the row
+/// commitments exactly as `compute_commitments` does (padding the data with 0s to the nearest
+/// power of 2) and comparing them with `commitment`.
+/// Returns `false`, rather than panicking, if the data is too long, does not fill a whole number
+/// of rows, or if the number of row commitments or blinding factors does not match the rows.
+pub fn verify_commitment<C: PrimeOrderCurve>(
+    data: &[u8],
+    commitment: &[C],
+    blinding_factors: &[C::Scalar],
+    vector_committer: &PedersenCommitter<C>,
+) -> bool {
+    first_mismatching_row(data, commitment, blinding_factors, vector_committer) == Ok(None)
+}
+
+/// Same as `verify_commitment` with the cache's committer, taking each row commitment from
+/// `cache` when it holds one. The rows are recommitted to one at a time, since the cache is
+/// updated on every row. See `RowCommitCache` for why the cache is server-side only.
+pub fn verify_commitment_with_cache<C: PrimeOrderCurve>(
+    data: &[u8],
+    commitment: &[C],
+    blinding_factors: &[C::Scalar],
+    cache: &mut RowCommitCache<C>,
+) -> bool {
+    let n_cols = cache.vector_committer().generators.len();
+    let data_vec = match padded_opening_data(data, n_cols, commitment, blinding_factors) {
+        Ok(data_vec) => data_vec,
+        Err(_) => return false,
+    };
+
+    data_vec
+        .chunks(n_cols)
+        .zip(blinding_factors.iter())
+        .zip(commitment.iter())
+        .all(|((chunk, blind), row_commitment)| {
+            cache.vector_commit(chunk, blind) == *row_commitment
+        })
+}
+
+/// Checks, e.g. on the phone, that the serialized commitment and blinding factors output by
+/// `compute_commitments_binary_outputs` open to `data`: each row of the data, padded exactly as
+/// the committer pads it, is recommitted to with its blinding factor (rather than from the seed)
+/// and compared with the deserialized row commitment.
+/// Errors with `HyraxError::RowCommitmentMismatch` holding the index of the first row that does
+/// not match, or with the deserialization or shape error if the inputs are malformed.
+pub fn verify_commitment_opening(
+    data: &[u8],
+    commitment_serialized: &[u8],
+    blinding_factors_serialized: &[u8],
+) -> Result<(), HyraxError> {
+    let vector_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    verify_commitment_opening_with_committer(
+        data,
+        commitment_serialized,
+        blinding_factors_serialized,
+        PointEncoding::Compressed,
+        &vector_committer,
+    )
+}
+
+/// Same as `verify_commitment_opening`, for row commitments encoded with `point_encoding` (e.g.
+/// the `point_encoding` of a `HyraxCommitmentOutputSerialized`) under `vector_committer`, so that
+/// a caller verifying many openings builds the committer once.
+pub fn verify_commitment_opening_with_committer<C: PrimeOrderCurve>(
+    data: &[u8],
+    commitment_serialized: &[u8],
+    blinding_factors_serialized: &[u8],
+    point_encoding: PointEncoding,
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<(), HyraxError> {
+    let commitment =
+        deserialize_commitment_with_encoding::<C>(commitment_serialized, point_encoding)?;
+    let blinding_factors = deserialize_scalars::<C>(blinding_factors_serialized)?;
+
+    match first_mismatching_row(data, &commitment, &blinding_factors, vector_committer)? {
+        Some(row) => Err(HyraxError::RowCommitmentMismatch(row)),
+        None => Ok(()),
+    }
+}
+
+// Recommits to the rows of `data` as `compute_commitments` does, returning the index of the first
+// row whose commitment under its blinding factor is not the one in `commitment`, if any.
+// Errors as `padded_opening_data` does if the shapes do not match.
+fn first_mismatching_row<C: PrimeOrderCurve>(
+    data: &[u8],
+    commitment: &[C],
+    blinding_factors: &[C::Scalar],
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<Option<usize>, HyraxError> {
+    let n_cols = vector_committer.generators.len();
+    let data_vec = padded_opening_data(data, n_cols, commitment, blinding_factors)?;
+
+    let is_mismatch = |((chunk, blind), row_commitment): ((&[u8], &C::Scalar), &C)| {
+        vector_committer.vector_commit(chunk, blind) != *row_commitment
+    };
+    #[cfg(feature = "rayon")]
+    let mismatch = data_vec
+        .par_chunks(n_cols)
+        .zip(blinding_factors.par_iter())
+        .zip(commitment.par_iter())
+        .position_first(is_mismatch);
+    #[cfg(not(feature = "rayon"))]
+    let mismatch = data_vec
+        .chunks(n_cols)
+        .zip(blinding_factors.iter())
+        .zip(commitment.iter())
+        .position(is_mismatch);
+    Ok(mismatch)
+}
+
+// Pads `data` as `compute_commitments` does. Errors if the data is too long, does not fill a
+// whole, nonzero number of rows of `n_cols` columns, or if the number of row commitments or
+// blinding factors does not match the rows.
+fn padded_opening_data<C: PrimeOrderCurve>(
+    data: &[u8],
+    n_cols: usize,
+    commitment: &[C],
+    blinding_factors: &[C::Scalar],
+) -> Result<Vec<u8>, HyraxError> {
+    let padded_len = validate_input_len(data.len(), MAX_INPUT_LEN)?;
+    validate_matrix_shape(padded_len, n_cols)?;
+    let n_rows = padded_len / n_cols;
+    if commitment.len() != n_rows {
+        return Err(HyraxError::RowCountMismatch {
+            expected: n_rows,
+            actual: commitment.len(),
+        });
+    }
+    if blinding_factors.len() != n_rows {
+        return Err(HyraxError::BlindingFactorCountMismatch {
+            expected: n_rows,
+            actual: blinding_factors.len(),
+        });
+    }
+    Ok(pad_to_power_of_two(data))
 }
 
 /// Helper functions for deserializing commitments/blinding factors from byte array
-pub fn deserialize_commitment_from_bytes_compressed<C: PrimeOrderCurve>(bytes: &[u8]) -> Vec<C> {
+/// Errors if the bytes do not split into a power-of-two number of compressed points,
+/// since the Hyrax matrix layout requires a power-of-two row count, or if a point fails to
+/// decode (see `PrimeOrderCurve::try_from_bytes_compressed`).
+pub fn deserialize_commitment_from_bytes_compressed<C: PrimeOrderCurve>(
+    bytes: &[u8],
+) -> Result<Vec<C>, HyraxError> {
+    validate_commitment_byte_length(bytes.len(), C::COMPRESSED_CURVE_POINT_BYTEWIDTH)?;
     let commitment = bytes
         .chunks(C::COMPRESSED_CURVE_POINT_BYTEWIDTH)
-        .map(|byte_repr| C::from_bytes_compressed(byte_repr))
-        .collect_vec();
-    commitment
+        .map(|byte_repr| C::try_from_bytes_compressed(byte_repr))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(commitment)
+}
+
+/// Same as `deserialize_commitment_from_bytes_compressed`, for row commitments serialized with
+/// `to_bytes_uncompressed`.
+pub fn deserialize_commitment_from_bytes_uncompressed<C: PrimeOrderCurve>(
+    bytes: &[u8],
+) -> Result<Vec<C>, HyraxError> {
+    validate_commitment_byte_length(bytes.len(), C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH)?;
+    bytes
+        .chunks(C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH)
+        .map(|byte_repr| C::try_from_bytes_uncompressed(byte_repr))
+        .collect()
+}
+
+/// Deserializes row commitments encoded with `point_encoding`.
+pub fn deserialize_commitment_with_encoding<C: PrimeOrderCurve>(
+    bytes: &[u8],
+    point_encoding: PointEncoding,
+) -> Result<Vec<C>, HyraxError> {
+    match point_encoding {
+        PointEncoding::Compressed => deserialize_commitment_from_bytes_compressed(bytes),
+        PointEncoding::Uncompressed => deserialize_commitment_from_bytes_uncompressed(bytes),
+    }
+}
+
+/// Sidecar to a compressed commitment that lets it be loaded without a square root per point:
+/// the little endian y-coordinate of each row commitment (zero for the point at infinity), as
+/// produced by `generate_hints` when the commitment is first validated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentHints {
+    pub y_coordinates: Vec<u8>,
+}
+
+/// Decompresses the commitment once and records the y-coordinate of each row commitment.
+/// Errors as `deserialize_commitment_from_bytes_compressed`.
+pub fn generate_hints<C: PrimeOrderCurve>(
+    commitment_bytes: &[u8],
+) -> Result<CommitmentHints, HyraxError> {
+    let coordinate_width = (C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH - 1) / 2;
+    let commitment = deserialize_commitment_from_bytes_compressed::<C>(commitment_bytes)?;
+    Ok(CommitmentHints {
+        y_coordinates: commitment
+            .iter()
+            .flat_map(|row_commitment| {
+                row_commitment.to_bytes_uncompressed()[1 + coordinate_width..].to_vec()
+            })
+            .collect(),
+    })
+}
+
+/// Same as `deserialize_commitment_from_bytes_compressed`, taking each y-coordinate from `hints`
+/// instead of computing a square root. Each hinted point goes through the uncompressed decoder,
+/// so the curve equation is still checked, and must re-encode to exactly its compressed bytes,
+/// which pins the parity of y; a forged hint therefore cannot yield an off-curve or a different
+/// point. Points whose hint fails either check, and all points if `hints` is absent or does not
+/// have one y-coordinate per point, are decompressed in full.
+/// Assumes the layouts of the crate's curves: a compressed point is the uncompressed one with
+/// the y-coordinate replaced by its parity byte.
+pub fn deserialize_commitment_with_hints<C: PrimeOrderCurve>(
+    bytes: &[u8],
+    hints: Option<&CommitmentHints>,
+) -> Result<Vec<C>, HyraxError> {
+    validate_commitment_byte_length(bytes.len(), C::COMPRESSED_CURVE_POINT_BYTEWIDTH)?;
+    let n_rows = bytes.len() / C::COMPRESSED_CURVE_POINT_BYTEWIDTH;
+    let coordinate_width = (C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH - 1) / 2;
+    match hints.filter(|hints| hints.y_coordinates.len() == n_rows * coordinate_width) {
+        Some(hints) => bytes
+            .chunks(C::COMPRESSED_CURVE_POINT_BYTEWIDTH)
+            .zip(hints.y_coordinates.chunks(coordinate_width))
+            .map(|(byte_repr, y_hint)| decompress_with_hint(byte_repr, y_hint))
+            .collect(),
+        None => deserialize_commitment_from_bytes_compressed(bytes),
+    }
+}
+
+// Decodes a compressed point using `y_hint` as its y-coordinate, falling back to full
+// decompression if the hinted point is off the curve or is not the one `byte_repr` encodes.
+fn decompress_with_hint<C: PrimeOrderCurve>(
+    byte_repr: &[u8],
+    y_hint: &[u8],
+) -> Result<C, HyraxError> {
+    let uncompressed = byte_repr[..byte_repr.len() - 1]
+        .iter()
+        .chain(y_hint.iter())
+        .copied()
+        .collect::<Vec<u8>>();
+    match C::try_from_bytes_uncompressed(&uncompressed) {
+        Ok(point) if point.to_bytes_compressed() == byte_repr => Ok(point),
+        _ => C::try_from_bytes_compressed(byte_repr),
+    }
+}
+
+/// Returns whether `bytes` is the canonical compressed serialization of a commitment, i.e.
+/// decoding each point and re-encoding it reproduces the bytes exactly. The decoder rejects
+/// unreduced x-coordinates, flag bytes other than 0 and 1, and points at infinity with nonzero
+/// trailing bytes, so every encoding that decodes is canonical; this is kept as a check that
+/// the decoder and encoder agree.
+/// Bytes that do not decode at all (e.g. an x-coordinate with no point on the curve) are not canonical.
+pub fn is_canonical_serialization<C: PrimeOrderCurve>(bytes: &[u8]) -> bool {
+    bytes.len() % C::COMPRESSED_CURVE_POINT_BYTEWIDTH == 0
+        && bytes
+            .chunks(C::COMPRESSED_CURVE_POINT_BYTEWIDTH)
+            .all(|byte_repr| {
+                C::try_from_bytes_compressed(byte_repr)
+                    .is_ok_and(|point| point.to_bytes_compressed() == byte_repr)
+            })
+}
+
+/// Checks that a serialized commitment of `len` bytes consists of a power-of-two
+/// number of points, each `point_width` bytes wide.
+pub fn validate_commitment_byte_length(len: usize, point_width: usize) -> Result<(), HyraxError> {
+    if len % point_width != 0 {
+        return Err(HyraxError::InvalidCommitmentLength { len, point_width });
+    }
+    let n_rows = len / point_width;
+    if !n_rows.is_power_of_two() {
+        return Err(HyraxError::NonPowerOfTwoRows(n_rows));
+    }
+    Ok(())
+}
+
+/// The result of comparing two commitments row by row, see `diff_commitments`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitmentDiff {
+    Equal,
+    /// The commitments have different numbers of rows.
+    DifferentLengths {
+        left_rows: usize,
+        right_rows: usize,
+    },
+    /// The commitments have the same number of rows, this being the first that differs.
+    FirstDifferingRow(usize),
+}
+
+/// Compares two commitments row by row.
+pub fn diff_commitments<C: PrimeOrderCurve>(left: &[C], right: &[C]) -> CommitmentDiff {
+    if left.len() != right.len() {
+        return CommitmentDiff::DifferentLengths {
+            left_rows: left.len(),
+            right_rows: right.len(),
+        };
+    }
+    match left.iter().zip(right.iter()).position(|(l, r)| l != r) {
+        Some(row) => CommitmentDiff::FirstDifferingRow(row),
+        None => CommitmentDiff::Equal,
+    }
+}
+
+/// Checks that `point` has one coordinate per variable of the multilinear extension committed to
+/// by `commitment`: `log2(commitment.len())` row variables followed by `log_num_cols` column
+/// variables. The opening prover and verifier should call this before expanding the point.
+pub fn validate_point_for_commitment<C: PrimeOrderCurve>(
+    point: &[C::Scalar],
+    commitment: &[C],
+    log_num_cols: usize,
+) -> Result<(), HyraxError> {
+    validate_point_len(point.len(), commitment.len(), log_num_cols)
+}
+
+fn validate_point_len(
+    point_len: usize,
+    n_rows: usize,
+    log_num_cols: usize,
+) -> Result<(), HyraxError> {
+    if !n_rows.is_power_of_two() {
+        return Err(HyraxError::NonPowerOfTwoRows(n_rows));
+    }
+    let expected = n_rows.ilog2() as usize + log_num_cols;
+    if point_len != expected {
+        return Err(HyraxError::InvalidEvaluationPointLength {
+            point_len,
+            expected,
+        });
+    }
+    Ok(())
 }
 
 pub fn deserialize_blinding_factors_from_bytes_compressed<C: PrimeOrderCurve>(
     bytes: &[u8],
-) -> Vec<C::Scalar> {
-    let blinding_factors: Vec<<C as PrimeOrderCurve>::Scalar> = bytes
-        .chunks(C::SCALAR_ELEM_BYTEWIDTH)
-        .map(C::Scalar::from_le_bytes_mod_order)
-        .collect_vec();
-    blinding_factors
+) -> Result<Vec<C::Scalar>, HyraxError> {
+    deserialize_scalars::<C>(bytes)
 }
 
-pub fn deserialize_commitment_from_bytes_compressed_concrete(bytes: &[u8]) -> Vec<Bn256Point> {
+pub fn deserialize_commitment_from_bytes_compressed_concrete(
+    bytes: &[u8],
+) -> Result<Vec<Bn256Point>, HyraxError> {
     deserialize_commitment_from_bytes_compressed(bytes)
 }
 
+pub fn deserialize_commitment_from_bytes_uncompressed_concrete(
+    bytes: &[u8],
+) -> Result<Vec<Bn256Point>, HyraxError> {
+    deserialize_commitment_from_bytes_uncompressed(bytes)
+}
+
 pub fn deserialize_blinding_factors_from_bytes_compressed_concrete(
     bytes: &[u8],
-) -> Vec<Bn256Scalar> {
+) -> Result<Vec<Bn256Scalar>, HyraxError> {
     deserialize_blinding_factors_from_bytes_compressed::<Bn256Point>(bytes)
 }
+
+/// Converts the row commitments into the normalized `G1Affine` points that the arkworks BN254
+/// pairing functions consume, bridging from the `PrimeOrderCurve` abstraction. The identity
+/// maps to `G1Affine::identity()`.
+/// The coordinates are carried over as-is, so this is only meaningful for implementations of
+/// the BN254 G1 curve (the only curve over `Bn256Base` here); the conversion asserts that each
+/// result is on the BN254 curve.
+pub fn commitment_as_g1_affine<C: PrimeOrderCurve<Base = Bn256Base>>(
+    commitment: &[C],
+) -> Vec<G1Affine> {
+    commitment
+        .iter()
+        .map(|point| match point.affine_coordinates() {
+            Some((x, y)) => {
+                let affine = G1Affine::new_unchecked(x, y);
+                assert!(affine.is_on_curve());
+                affine
+            }
+            None => G1Affine::identity(),
+        })
+        .collect()
+}
@@ -13,12 +13,11 @@ fn test_serialize_end_to_end() {
     use std::time::Instant;
 
     use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
     use ark_bn254::G1Projective as Bn256Point;
     use ark_ff::BigInteger;
     use ark_ff::PrimeField;
     use itertools::Itertools;
-    use rand::RngCore;
-    use rand_core::OsRng;
 
     // Read a dummy image from file
     let iris_image = read_bytes_from_file(INPUT_NORMALIZED_IMAGE_FILENAME);
@@ -26,8 +25,7 @@ fn test_serialize_end_to_end() {
     let start_time = Instant::now();
 
     // --- Sample randomness for the generation of the blinding factors ---
-    let mut seed = [0u8; 32];
-    OsRng.fill_bytes(&mut seed);
+    let seed = test_seed("test_serialize_end_to_end").bytes();
 
     // --- Compute generators ---
     let vector_committer: PedersenCommitter<Bn256Point> =
@@ -37,7 +35,7 @@ fn test_serialize_end_to_end() {
     let HyraxCommitmentOutput {
         commitment,
         blinding_factors,
-    } = compute_commitments(&iris_image, &vector_committer, seed);
+    } = compute_commitments(&iris_image, &vector_committer, seed).unwrap();
 
     println!("Computing commitment took: {:?}", start_time.elapsed());
 
@@ -68,12 +66,2029 @@ fn test_serialize_end_to_end() {
 
     // --- Deserialize from bytes ---
     let deserialized_commitment =
-        deserialize_commitment_from_bytes_compressed_concrete(&commitment_bytes_from_file);
-    let deserialized_blinding_factors = deserialize_blinding_factors_from_bytes_compressed_concrete(
-        &blinding_factors_bytes_from_file,
-    );
+        deserialize_commitment_from_bytes_compressed_concrete(&commitment_bytes_from_file).unwrap();
+    let deserialized_blinding_factors =
+        deserialize_blinding_factors_from_bytes_compressed_concrete(
+            &blinding_factors_bytes_from_file,
+        )
+        .unwrap();
 
     // --- Sanitycheck vs. original commitment/blinding factors ---
     assert_eq!(deserialized_commitment, commitment);
     assert_eq!(deserialized_blinding_factors, blinding_factors);
 }
+
+#[test]
+fn test_deserialize_rejects_non_power_of_two_rows() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::deserialize_commitment_from_bytes_compressed_concrete;
+    use ark_bn254::G1Projective as Bn256Point;
+    use itertools::Itertools;
+
+    let g = Bn256Point::generator();
+    let three_points = [g, g.double(), g + g.double()]
+        .iter()
+        .flat_map(|point| point.to_bytes_compressed())
+        .collect_vec();
+    assert_eq!(
+        deserialize_commitment_from_bytes_compressed_concrete(&three_points),
+        Err(HyraxError::NonPowerOfTwoRows(3))
+    );
+
+    // --- A trailing partial point is rejected before any decoding happens ---
+    let truncated = &three_points[..three_points.len() - 1];
+    assert!(matches!(
+        deserialize_commitment_from_bytes_compressed_concrete(truncated),
+        Err(HyraxError::InvalidCommitmentLength { .. })
+    ));
+
+    // --- A parity byte that is neither 0 nor 1 in one of the points ---
+    let mut four_points = [g, g.double(), g + g.double(), g.double().double()]
+        .iter()
+        .flat_map(|point| point.to_bytes_compressed())
+        .collect_vec();
+    four_points[Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH * 2 - 1] = 7;
+    assert!(matches!(
+        deserialize_commitment_from_bytes_compressed_concrete(&four_points),
+        Err(HyraxError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn test_rows_iterate_in_serialization_order() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_binary_outputs, LOG_NUM_COLS, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::BigInteger;
+    use ark_ff::PrimeField;
+
+    let data = (0..(1 << (LOG_NUM_COLS + 2)))
+        .map(|idx| (idx % 251) as u8)
+        .collect::<Vec<u8>>();
+    let seed = test_seed("test_rows_iterate_in_serialization_order").bytes();
+    let vector_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let output = compute_commitments(&data, &vector_committer, seed).unwrap();
+    let serialized = compute_commitments_binary_outputs(&data, seed).unwrap();
+
+    // --- Each row record lines up with the corresponding chunk of the serialized output ---
+    let point_chunks = serialized
+        .commitment_serialized
+        .chunks(Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH);
+    let scalar_chunks = serialized
+        .blinding_factors_serialized
+        .chunks(Bn256Point::SCALAR_ELEM_BYTEWIDTH);
+    let mut n_rows = 0;
+    for ((row, point_bytes), scalar_bytes) in output.rows().zip(point_chunks).zip(scalar_chunks) {
+        assert_eq!(row.index, n_rows);
+        assert_eq!(row.commitment.to_bytes_compressed(), point_bytes);
+        assert_eq!(
+            row.blinding_factor.into_bigint().to_bytes_le(),
+            scalar_bytes
+        );
+        assert_eq!(output.get_row(row.index), Some(row));
+        n_rows += 1;
+    }
+    assert_eq!(n_rows, 4);
+    assert_eq!(output.get_row(n_rows), None);
+
+    // --- Owned iteration yields the same triples ---
+    let borrowed = output
+        .rows()
+        .map(|row| (row.index, *row.commitment, *row.blinding_factor))
+        .collect::<Vec<_>>();
+    let owned = output.into_iter().collect::<Vec<_>>();
+    assert_eq!(borrowed, owned);
+}
+
+#[test]
+fn test_bit_planes_reconstruct_byte_commitment() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        commit_bit_planes, generate_blinding_factors, MAX_INPUT_LEN, NUM_BIT_PLANES,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, "accountable magic something something");
+    let data = (0..32)
+        .map(|idx| (idx * 37 + 11) as u8)
+        .collect::<Vec<u8>>();
+    let planes =
+        commit_bit_planes(&data, &committer, test_seed("test_bit_planes").bytes()).unwrap();
+    assert_eq!(planes.len(), NUM_BIT_PLANES);
+
+    // --- sum_k 2^k * C_k is a commitment to the bytes under sum_k 2^k * r_k ---
+    for (row_idx, row) in data.chunks(8).enumerate() {
+        let (combined_commit, combined_blinding) = planes.iter().enumerate().fold(
+            (Bn256Point::default(), Bn256Scalar::from(0u64)),
+            |(commit_acc, blinding_acc), (plane_idx, plane)| {
+                let weight = Bn256Scalar::from(1u64 << plane_idx);
+                (
+                    commit_acc + plane.commitment[row_idx] * weight,
+                    blinding_acc + plane.blinding_factors[row_idx] * weight,
+                )
+            },
+        );
+        assert_eq!(
+            combined_commit,
+            committer.vector_commit(row, &combined_blinding)
+        );
+    }
+
+    // --- The blinding factors are those of generate_blinding_factors, plane after plane ---
+    let blinding_factors = generate_blinding_factors::<Bn256Point>(
+        test_seed("test_bit_planes").bytes(),
+        NUM_BIT_PLANES * 4,
+    );
+    assert!(planes
+        .iter()
+        .zip(blinding_factors.chunks(4))
+        .all(|(plane, expected)| plane.blinding_factors == expected));
+
+    // --- Inputs over MAX_INPUT_LEN are rejected before any padding or bit expansion ---
+    assert_eq!(
+        commit_bit_planes(
+            &vec![0u8; MAX_INPUT_LEN + 1],
+            &committer,
+            test_seed("test_bit_planes").bytes()
+        )
+        .err(),
+        Some(HyraxError::InputTooLarge {
+            len: MAX_INPUT_LEN + 1,
+            max: MAX_INPUT_LEN
+        })
+    );
+}
+
+#[test]
+fn test_commit_rle_matches_expanded_data() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{commit_rle, compute_commitments};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, "accountable magic something something");
+    let seed = test_seed("test_commit_rle").bytes();
+
+    // --- Runs that straddle row boundaries, with an expanded length that needs padding ---
+    let runs = vec![(0_u8, 5), (255, 6), (17, 1), (0, 9), (3, 7)];
+    let expanded = runs
+        .iter()
+        .flat_map(|(value, run_len)| std::iter::repeat(*value).take(*run_len))
+        .collect::<Vec<u8>>();
+    assert_eq!(expanded.len(), 28);
+
+    let rle_output = commit_rle(&runs, &committer, seed).unwrap();
+    let expanded_output = compute_commitments(&expanded, &committer, seed).unwrap();
+    assert_eq!(rle_output.commitment.len(), 4);
+    assert_eq!(rle_output.commitment, expanded_output.commitment);
+    assert_eq!(
+        rle_output.blinding_factors,
+        expanded_output.blinding_factors
+    );
+
+    // --- Too little data to fill a single row ---
+    assert_eq!(
+        commit_rle(&[(1, 3)], &committer, seed).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 3,
+            n_cols: 8
+        })
+    );
+}
+
+#[test]
+fn test_unpadded_final_row_uses_only_its_elements() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::compute_commitments_unpadded;
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, "accountable magic something something");
+    // --- Two full rows and a final row of 3 elements ---
+    let data = (1..=19).collect::<Vec<u8>>();
+    let unpadded =
+        compute_commitments_unpadded(&data, &committer, test_seed("unpadded").bytes()).unwrap();
+    assert_eq!(unpadded.last_row_len, 3);
+    assert_eq!(unpadded.output.commitment.len(), 3);
+
+    let last_blinding = unpadded.output.blinding_factors[2];
+    let (expected_last_commit, used_generators) =
+        committer.vector_commit_with_used_generators(&data[16..], &last_blinding);
+    assert_eq!(used_generators.len(), 3);
+    assert_eq!(unpadded.output.commitment[2], expected_last_commit);
+
+    // --- Empty data, and a committer without generators, are errors rather than panics ---
+    assert_eq!(
+        compute_commitments_unpadded(&[], &committer, test_seed("unpadded").bytes()).err(),
+        Some(HyraxError::EmptyInput)
+    );
+    let (empty, _) = committer.split_at(0);
+    assert_eq!(
+        compute_commitments_unpadded(&data, &empty, test_seed("unpadded").bytes()).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 19,
+            n_cols: 0
+        })
+    );
+}
+
+#[test]
+fn test_input_length_boundary() {
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_with_max_len, validate_input_len, MAX_INPUT_LEN,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- The bound itself is checked without allocating any data ---
+    assert_eq!(
+        validate_input_len(MAX_INPUT_LEN, MAX_INPUT_LEN),
+        Ok(MAX_INPUT_LEN)
+    );
+    assert_eq!(
+        validate_input_len(MAX_INPUT_LEN + 1, MAX_INPUT_LEN),
+        Err(HyraxError::InputTooLarge {
+            len: MAX_INPUT_LEN + 1,
+            max: MAX_INPUT_LEN
+        })
+    );
+    // --- Lengths whose padded size would overflow are rejected, not wrapped ---
+    assert!(validate_input_len(usize::MAX, usize::MAX).is_err());
+
+    // --- compute_commitments_with_max_len enforces the bound at max and max + 1 ---
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "accountable magic something something");
+    let data = vec![1_u8; 16];
+    assert!(compute_commitments_with_max_len(&data, &committer, [0; 32], 16).is_ok());
+    assert_eq!(
+        compute_commitments_with_max_len(&data, &committer, [0; 32], 15).err(),
+        Some(HyraxError::InputTooLarge { len: 16, max: 15 })
+    );
+}
+
+#[test]
+fn test_salts_change_commitment() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{compute_commitments, compute_commitments_salted};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, "accountable magic something something");
+    let data = (0..28).collect::<Vec<u8>>();
+    let seed = test_seed("salted").bytes();
+
+    let salted_1 = compute_commitments_salted(&data, b"nonce-1", &committer, seed).unwrap();
+    let salted_2 = compute_commitments_salted(&data, b"nonce-2", &committer, seed).unwrap();
+    assert_eq!(salted_1.salt_len, 7);
+    assert_ne!(salted_1.output.commitment, salted_2.output.commitment);
+
+    // --- The salt is prepended before chunking and padding ---
+    let manually_salted = b"nonce-1"
+        .iter()
+        .chain(data.iter())
+        .copied()
+        .collect::<Vec<u8>>();
+    let expected = compute_commitments(&manually_salted, &committer, seed).unwrap();
+    assert_eq!(salted_1.output.commitment, expected.commitment);
+}
+
+#[test]
+fn test_canonical_serialization() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{compute_commitments_binary_outputs, is_canonical_serialization};
+    use ark_bn254::Fq as Bn256Base;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::{BigInteger, PrimeField};
+
+    let data = (0..1024).map(|idx| (idx % 256) as u8).collect::<Vec<u8>>();
+    let serialized = compute_commitments_binary_outputs(&data, test_seed("canonical")).unwrap();
+    assert!(is_canonical_serialization::<Bn256Point>(
+        &serialized.commitment_serialized
+    ));
+
+    // --- The generator (x = 1) encoded with x + p instead of x is rejected ---
+    let generator_bytes = <Bn256Point as PrimeOrderCurve>::generator().to_bytes_compressed();
+    let mut x_plus_modulus = Bn256Base::MODULUS;
+    x_plus_modulus.add_with_carry(&1_u64.into());
+    let mut non_canonical = generator_bytes.clone();
+    non_canonical[1..33].copy_from_slice(&x_plus_modulus.to_bytes_le());
+    assert_eq!(
+        Bn256Point::try_from_bytes_compressed(&non_canonical),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+    assert!(is_canonical_serialization::<Bn256Point>(&generator_bytes));
+    assert!(!is_canonical_serialization::<Bn256Point>(&non_canonical));
+
+    // --- The point at infinity with junk after the flag byte ---
+    let junk_infinity = vec![1_u8; Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH];
+    assert!(!is_canonical_serialization::<Bn256Point>(&junk_infinity));
+    let infinity_bytes = <Bn256Point as PrimeOrderCurve>::zero().to_bytes_compressed();
+    assert!(is_canonical_serialization::<Bn256Point>(&infinity_bytes));
+}
+
+#[test]
+fn test_regenerated_blinding_factors_reopen_commitment() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs,
+        deserialize_blinding_factors_from_bytes_compressed_concrete,
+        deserialize_commitment_from_bytes_compressed_concrete, regenerate_blinding_factors,
+        BlindingDerivation, LOG_NUM_COLS, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let seed = test_seed("escrowed seed");
+    let data = (0..(4 << LOG_NUM_COLS) - 100)
+        .map(|idx| (idx % 256) as u8)
+        .collect::<Vec<u8>>();
+    let serialized = compute_commitments_binary_outputs(&data, seed).unwrap();
+    let n_rows =
+        serialized.commitment_serialized.len() / Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH;
+    assert_eq!(n_rows, 4);
+
+    // --- Later: only the seed and the shape are known ---
+    let regenerated = regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::default());
+    assert_eq!(regenerated, serialized.blinding_factors_serialized);
+
+    // --- The regenerated factors open the earlier commitment ---
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let commitment =
+        deserialize_commitment_from_bytes_compressed_concrete(&serialized.commitment_serialized)
+            .unwrap();
+    let blinding_factors =
+        deserialize_blinding_factors_from_bytes_compressed_concrete(&regenerated).unwrap();
+    let mut padded_data = data.clone();
+    padded_data.resize(4 << LOG_NUM_COLS, 0);
+    for ((row, blind), row_commitment) in padded_data
+        .chunks(1 << LOG_NUM_COLS)
+        .zip(blinding_factors.iter())
+        .zip(commitment.iter())
+    {
+        assert_eq!(committer.vector_commit(row, blind), *row_commitment);
+    }
+}
+
+#[test]
+fn test_regenerate_blinding_factors_for_each_derivation() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments_parallel, compute_commitments_with_rng_kind,
+        regenerate_blinding_factors, BlindingDerivation, HyraxCommitmentOutput, RngKind,
+        PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use crate::scalar_serde::serialize_scalars;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let seed = test_seed("escrowed seed");
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data = (0..100).map(|idx| idx as u8).collect::<Vec<u8>>();
+    let n_rows = 8;
+
+    // --- Each derivation regenerates the factors of the matching commitment function ---
+    for rng_kind in [RngKind::ChaCha8, RngKind::ChaCha12, RngKind::ChaCha20] {
+        let output: HyraxCommitmentOutput<Bn256Point> =
+            compute_commitments_with_rng_kind(&data, &committer, seed.bytes(), rng_kind).unwrap();
+        assert_eq!(
+            regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::Sequential(rng_kind)),
+            serialize_scalars::<Bn256Point>(&output.blinding_factors)
+        );
+    }
+    let output = compute_commitments_parallel(&data, &committer, seed.bytes()).unwrap();
+    assert_eq!(
+        regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::PerRow),
+        serialize_scalars::<Bn256Point>(&output.blinding_factors)
+    );
+
+    // --- The derivations disagree, so the right one must be supplied ---
+    assert_ne!(
+        regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::default()),
+        regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::PerRow)
+    );
+    assert_ne!(
+        regenerate_blinding_factors(&seed, n_rows, BlindingDerivation::default()),
+        regenerate_blinding_factors(
+            &seed,
+            n_rows,
+            BlindingDerivation::Sequential(RngKind::ChaCha12)
+        )
+    );
+}
+
+#[test]
+fn test_rng_kinds_are_deterministic_and_distinct() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_with_rng_kind, RngKind, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let data: Vec<u8> = (0..16).collect();
+    let seed = test_seed("rng kinds").bytes();
+
+    let outputs = [RngKind::ChaCha8, RngKind::ChaCha12, RngKind::ChaCha20].map(|rng_kind| {
+        let first = compute_commitments_with_rng_kind(&data, &committer, seed, rng_kind).unwrap();
+        let second = compute_commitments_with_rng_kind(&data, &committer, seed, rng_kind).unwrap();
+        assert_eq!(first.commitment, second.commitment);
+        assert_eq!(first.blinding_factors, second.blinding_factors);
+        first
+    });
+
+    // --- Each variant expands the same seed differently ---
+    assert_ne!(outputs[0].blinding_factors, outputs[1].blinding_factors);
+    assert_ne!(outputs[1].blinding_factors, outputs[2].blinding_factors);
+    assert_ne!(outputs[0].blinding_factors, outputs[2].blinding_factors);
+
+    // --- The default is the ChaCha20 stream used by `compute_commitments` ---
+    assert_eq!(RngKind::default(), RngKind::ChaCha20);
+    let default_output = compute_commitments(&data, &committer, seed).unwrap();
+    assert_eq!(default_output.commitment, outputs[2].commitment);
+}
+
+#[test]
+fn test_validate_point_for_commitment() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, validate_point_for_commitment, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- 4 rows of 8 columns: 2 row variables and 3 column variables ---
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let data: Vec<u8> = (0..32).collect();
+    let commitment = compute_commitments(&data, &committer, test_seed("validate point").bytes())
+        .unwrap()
+        .commitment;
+
+    let point = vec![Bn256Scalar::from(7u64); 5];
+    assert_eq!(
+        validate_point_for_commitment(&point, &commitment, 3),
+        Ok(())
+    );
+    assert_eq!(
+        validate_point_for_commitment(&point[..4], &commitment, 3),
+        Err(HyraxError::InvalidEvaluationPointLength {
+            point_len: 4,
+            expected: 5
+        })
+    );
+}
+
+#[test]
+fn test_blinding_commitment_opens_to_blinding_factors() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        blinding_commitment_committer, compute_commitments,
+        compute_commitments_with_blinding_commitment, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let data: Vec<u8> = (0..64).collect();
+    let seed = test_seed("blinding commitment").bytes();
+    let committed = compute_commitments_with_blinding_commitment(&data, &committer, seed).unwrap();
+
+    // --- The row commitments are the usual ones ---
+    let plain = compute_commitments(&data, &committer, seed).unwrap();
+    assert_eq!(committed.output.commitment, plain.commitment);
+    assert_eq!(committed.output.blinding_factors, plain.blinding_factors);
+
+    // --- Open the blinding commitment naively ---
+    let blinding_committer: PedersenCommitter<Bn256Point> = blinding_commitment_committer(8);
+    let opened = plain
+        .blinding_factors
+        .iter()
+        .zip(blinding_committer.generators.iter())
+        .fold(
+            blinding_committer.blinding_generator * committed.blinding_commitment_blinding_factor,
+            |acc, (blinding_factor, generator)| acc + *generator * *blinding_factor,
+        );
+    assert_eq!(committed.blinding_commitment, opened);
+    assert_ne!(committed.blinding_commitment, Bn256Point::zero());
+}
+
+#[test]
+fn test_signable_bytes_match_serialized_commitment() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        commit_for_signing, compute_commitments_binary_outputs, LOG_NUM_COLS, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+    use blake2::{Blake2s256, Digest};
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let data: Vec<u8> = (0..(2 << LOG_NUM_COLS)).map(|i| (i % 199) as u8).collect();
+    let seed = test_seed("signable bytes").bytes();
+
+    let signable = commit_for_signing(&data, &committer, seed).unwrap();
+    let serialized = compute_commitments_binary_outputs(&data, seed).unwrap();
+    assert_eq!(signable.commitment_bytes, serialized.commitment_serialized);
+    assert_eq!(
+        signable.digest,
+        <[u8; 32]>::from(Blake2s256::digest(&serialized.commitment_serialized))
+    );
+}
+
+#[test]
+fn test_sliding_windows_overlap_and_verify() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{commit_sliding_windows, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let data: Vec<u8> = (10..21).collect();
+    let output = commit_sliding_windows(
+        &data,
+        4,
+        2,
+        &committer,
+        test_seed("sliding windows").bytes(),
+    )
+    .unwrap();
+
+    // --- windows start at 0, 2, 4 and 6; the trailing (10..21)[8..11] is not a full window ---
+    assert_eq!(output.commitment.len(), 4);
+    for (idx, (row_commitment, blind)) in output
+        .commitment
+        .iter()
+        .zip(output.blinding_factors.iter())
+        .enumerate()
+    {
+        let window = &data[idx * 2..idx * 2 + 4];
+        assert_eq!(*row_commitment, committer.vector_commit(window, blind));
+    }
+    // adjacent windows share data[2..4]
+    assert_eq!(&data[0..4][2..], &data[2..6][..2]);
+
+    assert_eq!(
+        commit_sliding_windows(
+            &data,
+            5,
+            2,
+            &committer,
+            test_seed("sliding windows").bytes()
+        )
+        .err(),
+        Some(HyraxError::InvalidWindow {
+            window: 5,
+            stride: 2,
+            n_cols: 4
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_parallel_commitments_independent_of_thread_count() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments_parallel, derive_row_blinding, derive_row_blindings, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let data: Vec<u8> = (0..128).map(|i| (i * 7 % 256) as u8).collect();
+    let seed = test_seed("thread count").bytes();
+
+    let outputs: Vec<_> = [1, 2, 5]
+        .iter()
+        .map(|num_threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(*num_threads)
+                .build()
+                .unwrap()
+                .install(|| compute_commitments_parallel(&data, &committer, seed).unwrap())
+        })
+        .collect();
+    for output in outputs.iter() {
+        assert_eq!(output.blinding_factors, outputs[0].blinding_factors);
+        assert_eq!(output.commitment, outputs[0].commitment);
+    }
+
+    // --- Each row's blinding factor is computable on its own ---
+    let blindings = derive_row_blindings::<Bn256Point>(seed, 16);
+    assert_eq!(blindings, outputs[0].blinding_factors);
+    assert_eq!(blindings[11], derive_row_blinding::<Bn256Point>(seed, 11));
+    assert_ne!(blindings[0], blindings[1]);
+}
+
+#[test]
+fn test_diff_commitments() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::iriscode_commit::{diff_commitments, CommitmentDiff};
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let g = Bn256Point::generator();
+    let commitment = vec![g, g.double(), g.double().double()];
+    let mut tampered = commitment.clone();
+    tampered[1] = g;
+
+    assert_eq!(
+        diff_commitments(&commitment, &commitment),
+        CommitmentDiff::Equal
+    );
+    assert_eq!(
+        diff_commitments(&commitment, &tampered),
+        CommitmentDiff::FirstDifferingRow(1)
+    );
+    assert_eq!(
+        diff_commitments(&commitment, &commitment[..2]),
+        CommitmentDiff::DifferentLengths {
+            left_rows: 3,
+            right_rows: 2
+        }
+    );
+}
+
+#[test]
+fn test_evaluation_proof_round_trip() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments, prove_evaluation, tensor_expand, verify_evaluation, LOG_NUM_COLS,
+        PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::UniformRand;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    let mut rng = ChaCha20Rng::from_seed(test_seed("evaluation proof rng").bytes());
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let mut data = vec![0u8; 1 << 17];
+    rng.fill_bytes(&mut data);
+    let output =
+        compute_commitments(&data, &committer, test_seed("evaluation proof").bytes()).unwrap();
+
+    // --- 8 row coordinates followed by LOG_NUM_COLS column coordinates ---
+    let point: Vec<Bn256Scalar> = (0..17).map(|_| Bn256Scalar::rand(&mut rng)).collect();
+    let (row_point, col_point) = point.split_at(17 - LOG_NUM_COLS);
+    // the element at index row * n_cols + col has the column coordinates as its low bits
+    let full_point = [col_point, row_point].concat();
+    let claimed_evaluation = data
+        .iter()
+        .zip(tensor_expand(&full_point).iter())
+        .fold(Bn256Scalar::from(0u64), |acc, (element, weight)| {
+            acc + Bn256Scalar::from(*element as u64) * weight
+        });
+
+    let proof = prove_evaluation(&data, &output.blinding_factors, &point, &committer).unwrap();
+    assert!(verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation,
+        &proof,
+        &committer
+    ));
+
+    // --- A wrong claim or a mutated proof does not verify ---
+    let one = Bn256Scalar::from(1u64);
+    assert!(!verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation + one,
+        &proof,
+        &committer
+    ));
+    let mut mutated = proof.clone();
+    mutated.row_combination[3] += one;
+    assert!(!verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation,
+        &mutated,
+        &committer
+    ));
+    let mut mutated = proof.clone();
+    mutated.combined_blinding_factor += one;
+    assert!(!verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation,
+        &mutated,
+        &committer
+    ));
+    assert!(!verify_evaluation(
+        &output.commitment,
+        &point[1..],
+        claimed_evaluation,
+        &proof,
+        &committer
+    ));
+}
+
+#[test]
+fn test_delta_commitment_plus_baseline_recovers_commitment() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{commit_delta_from_baseline, compute_commitments, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let data: Vec<u8> = (0..30).map(|i| (i * 37 % 256) as u8).collect();
+    let baseline: Vec<u8> = (0..30).map(|i| (255 - i * 11 % 256) as u8).collect();
+    let seed = test_seed("delta").bytes();
+
+    let delta = commit_delta_from_baseline(&data, &baseline, &committer, seed).unwrap();
+    let direct = compute_commitments(&data, &committer, seed).unwrap();
+    assert_eq!(delta.blinding_factors, direct.blinding_factors);
+
+    // --- The public baseline commitment is unblinded ---
+    let mut padded_baseline = baseline.clone();
+    padded_baseline.resize(32, 0);
+    for ((delta_row, direct_row), baseline_row) in delta
+        .commitment
+        .iter()
+        .zip(direct.commitment.iter())
+        .zip(padded_baseline.chunks(8))
+    {
+        let baseline_commit = committer.vector_commit(baseline_row, &Bn256Scalar::from(0u64));
+        assert_eq!(*delta_row + baseline_commit, *direct_row);
+    }
+
+    assert_eq!(
+        commit_delta_from_baseline(&data, &baseline[1..], &committer, seed).err(),
+        Some(HyraxError::BaselineLengthMismatch {
+            data_len: 30,
+            baseline_len: 29
+        })
+    );
+}
+
+#[test]
+fn test_compute_commitments_row_count() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{compute_commitments, LOG_NUM_COLS, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let data: Vec<u8> = (0..(1 << 17)).map(|i| (i % 251) as u8).collect();
+    let output = compute_commitments(&data, &committer, test_seed("row count").bytes()).unwrap();
+    assert_eq!(output.commitment.len(), (1 << 17) >> LOG_NUM_COLS);
+
+    // --- Rows stay in order: the parallel result matches committing row by row ---
+    for ((row, blind), row_commitment) in data
+        .chunks(1 << LOG_NUM_COLS)
+        .zip(output.blinding_factors.iter())
+        .zip(output.commitment.iter())
+        .step_by(37)
+    {
+        assert_eq!(committer.vector_commit(row, blind), *row_commitment);
+    }
+}
+
+#[test]
+fn test_binary_outputs_with_shape() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs, compute_commitments_binary_outputs_with_shape,
+        deserialize_blinding_factors_from_bytes_compressed_concrete,
+        deserialize_commitment_from_bytes_compressed_concrete, LOG_NUM_COLS,
+    };
+
+    let data: Vec<u8> = (0..(1 << 12)).map(|i| (i % 211) as u8).collect();
+    let seed = test_seed("with shape");
+
+    for log_num_cols in [4, 6, LOG_NUM_COLS] {
+        let output =
+            compute_commitments_binary_outputs_with_shape(&data, seed, log_num_cols).unwrap();
+        let commitment =
+            deserialize_commitment_from_bytes_compressed_concrete(&output.commitment_serialized)
+                .unwrap();
+        let blinding_factors = deserialize_blinding_factors_from_bytes_compressed_concrete(
+            &output.blinding_factors_serialized,
+        )
+        .unwrap();
+        assert_eq!(commitment.len(), 1 << (12 - log_num_cols));
+        assert_eq!(blinding_factors.len(), commitment.len());
+    }
+
+    // --- The default shape is the existing Orb path ---
+    assert_eq!(
+        compute_commitments_binary_outputs_with_shape(&data, seed, LOG_NUM_COLS)
+            .unwrap()
+            .commitment_serialized,
+        compute_commitments_binary_outputs(&data, seed)
+            .unwrap()
+            .commitment_serialized
+    );
+
+    assert_eq!(
+        compute_commitments_binary_outputs_with_shape(&data, seed, 13).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 1 << 12,
+            n_cols: 1 << 13
+        })
+    );
+
+    // --- Only the explicit shape rejects data shorter than a row; the default keeps its
+    // original behaviour and commits to no rows ---
+    let short_output = compute_commitments_binary_outputs(&data[..100], seed).unwrap();
+    assert!(short_output.commitment_serialized.is_empty());
+    assert!(short_output.blinding_factors_serialized.is_empty());
+    assert_eq!(
+        compute_commitments_binary_outputs_with_shape(&data[..100], seed, LOG_NUM_COLS).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 128,
+            n_cols: 1 << LOG_NUM_COLS
+        })
+    );
+}
+
+#[test]
+fn test_binary_outputs_with_params() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs, compute_commitments_binary_outputs_with_params,
+        compute_commitments_binary_outputs_with_shape, LOG_NUM_COLS,
+    };
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- Not a power of two, so padded to 1 << 12 ---
+    let data: Vec<u8> = (0..3000).map(|i| (i % 199) as u8).collect();
+    let seed = test_seed("with params");
+
+    for log_num_cols in [4, 7, 9] {
+        let output =
+            compute_commitments_binary_outputs_with_params(&data, seed, log_num_cols).unwrap();
+        let n_rows = 1 << (12 - log_num_cols);
+        assert_eq!(
+            output.commitment_serialized.len(),
+            n_rows * Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH
+        );
+        assert_eq!(
+            output.blinding_factors_serialized.len(),
+            n_rows * Bn256Point::SCALAR_ELEM_BYTEWIDTH
+        );
+        assert_eq!(
+            output.commitment_serialized,
+            compute_commitments_binary_outputs_with_shape(&data, seed, log_num_cols)
+                .unwrap()
+                .commitment_serialized
+        );
+    }
+    assert_eq!(
+        compute_commitments_binary_outputs_with_params(&data, seed, LOG_NUM_COLS)
+            .unwrap()
+            .commitment_serialized,
+        compute_commitments_binary_outputs(&data, seed)
+            .unwrap()
+            .commitment_serialized
+    );
+
+    // --- Both entry points reject a single column, reporting the padded length ---
+    for log_num_cols_0 in [
+        compute_commitments_binary_outputs_with_params(&data, seed, 0),
+        compute_commitments_binary_outputs_with_shape(&data, seed, 0),
+    ] {
+        assert_eq!(
+            log_num_cols_0.err(),
+            Some(HyraxError::InvalidMatrixShape {
+                data_len: 1 << 12,
+                n_cols: 1
+            })
+        );
+    }
+}
+
+#[test]
+fn test_evaluation_proof_for_signed_data() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        commit_delta_from_baseline, prove_evaluation_for_scalars, tensor_expand, verify_evaluation,
+        PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- Commit to signed data in the i8 range as a delta from a zero baseline... ---
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let signed: Vec<i16> = (0..32).map(|i| ((i * 29) % 256) as i16 - 128).collect();
+    let baseline = vec![128u8; 32];
+    let data: Vec<u8> = signed.iter().map(|value| (value + 128) as u8).collect();
+    let output = commit_delta_from_baseline(
+        &data,
+        &baseline,
+        &committer,
+        test_seed("signed data").bytes(),
+    )
+    .unwrap();
+
+    // --- ...and open it as signed field elements ---
+    let scalar_data: Vec<Bn256Scalar> = signed
+        .iter()
+        .map(|value| {
+            let magnitude = Bn256Scalar::from(value.unsigned_abs() as u64);
+            if *value < 0 {
+                -magnitude
+            } else {
+                magnitude
+            }
+        })
+        .collect();
+    let point: Vec<Bn256Scalar> = (1..=5u64)
+        .map(|i| Bn256Scalar::from(i * 1000 + 7))
+        .collect();
+    let (row_point, col_point) = point.split_at(2);
+    let claimed_evaluation = scalar_data
+        .iter()
+        .zip(tensor_expand(&[col_point, row_point].concat()).iter())
+        .fold(Bn256Scalar::from(0u64), |acc, (element, weight)| {
+            acc + *element * weight
+        });
+
+    let proof =
+        prove_evaluation_for_scalars(&scalar_data, &output.blinding_factors, &point, &committer)
+            .unwrap();
+    assert!(verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation,
+        &proof,
+        &committer
+    ));
+}
+
+#[cfg(feature = "ark-serialize")]
+#[test]
+fn test_canonical_serialize_matches_crate_serialization() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_binary_outputs, HyraxCommitmentOutput,
+        LOG_NUM_COLS, MAX_INPUT_LEN, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let data: Vec<u8> = (0..(4 << LOG_NUM_COLS)).map(|i| (i % 233) as u8).collect();
+    let seed = test_seed("ark serialize").bytes();
+    let output = compute_commitments(&data, &committer, seed).unwrap();
+
+    let mut bytes = vec![];
+    output.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), output.compressed_size());
+
+    // --- Row count, then exactly the crate's own serialization ---
+    let serialized = compute_commitments_binary_outputs(&data, seed).unwrap();
+    assert_eq!(bytes[..8], 4u64.to_le_bytes());
+    let (commitment_bytes, blinding_factor_bytes) =
+        bytes[8..].split_at(serialized.commitment_serialized.len());
+    assert_eq!(commitment_bytes, serialized.commitment_serialized);
+    assert_eq!(
+        blinding_factor_bytes,
+        serialized.blinding_factors_serialized
+    );
+
+    let round_tripped =
+        HyraxCommitmentOutput::<Bn256Point>::deserialize_compressed(&bytes[..]).unwrap();
+    assert_eq!(round_tripped.commitment, output.commitment);
+    assert_eq!(round_tripped.blinding_factors, output.blinding_factors);
+
+    // --- Truncated input errors rather than panicking ---
+    assert!(
+        HyraxCommitmentOutput::<Bn256Point>::deserialize_compressed(&bytes[..bytes.len() - 1])
+            .is_err()
+    );
+
+    // --- A forged row count without the rows errors on the first missing row ---
+    let forged_header = (MAX_INPUT_LEN as u64).to_le_bytes();
+    assert!(
+        HyraxCommitmentOutput::<Bn256Point>::deserialize_compressed(&forged_header[..]).is_err()
+    );
+}
+
+#[test]
+fn test_parallel_commitments_match_sequential() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{compute_commitments, generate_blinding_factors, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(128, PUBLIC_STRING);
+    let data: Vec<u8> = (0..(64 * 128)).map(|i| (i * 13 % 256) as u8).collect();
+    let seed = test_seed("parallel").bytes();
+
+    // --- The sequential reference: blinding factors first, then one row at a time ---
+    let blinding_factors = generate_blinding_factors::<Bn256Point>(seed, 64);
+    let sequential: Vec<Bn256Point> = data
+        .chunks(128)
+        .zip(blinding_factors.iter())
+        .map(|(row, blind)| committer.vector_commit(row, blind))
+        .collect();
+
+    let parallel = compute_commitments(&data, &committer, seed).unwrap();
+    assert_eq!(parallel.commitment, sequential);
+    assert_eq!(parallel.blinding_factors, blinding_factors);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_commitments_in_thread_pools_match_compute_commitments() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_in_pool, compute_commitments_with_max_threads,
+        PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(128, PUBLIC_STRING);
+    let data: Vec<u8> = (0..(64 * 128)).map(|i| (i * 13 % 256) as u8).collect();
+    let seed = test_seed("parallel").bytes();
+    let expected = compute_commitments(&data, &committer, seed).unwrap();
+
+    for max_threads in [1, 3] {
+        let capped =
+            compute_commitments_with_max_threads(&data, &committer, seed, max_threads).unwrap();
+        assert_eq!(capped.commitment, expected.commitment);
+        assert_eq!(capped.blinding_factors, expected.blinding_factors);
+    }
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    let pooled = compute_commitments_in_pool(&data, &committer, seed, &thread_pool).unwrap();
+    assert_eq!(pooled.commitment, expected.commitment);
+
+    assert_eq!(
+        compute_commitments_with_max_threads(&data, &committer, seed, 0).map(|_| ()),
+        Err(HyraxError::InvalidThreadCount)
+    );
+}
+
+#[test]
+fn test_joint_commitment_opens_each_channel() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        commit_joint, interleave_joint, prove_evaluation, tensor_expand, verify_evaluation,
+        JointChannel, JointCommitmentLayout, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::{UniformRand, Zero};
+    use itertools::Itertools;
+
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    // --- 100 elements per channel, so both channels and the joint data get padded ---
+    let iris = (0..100).map(|i| (i * 37 % 256) as u8).collect_vec();
+    let mask = (0..100).map(|i| i % 3 != 0).collect_vec();
+    let joint = commit_joint(&iris, &mask, &committer, test_seed("joint").bytes()).unwrap();
+    assert_eq!(joint.output.commitment.len(), 256 / 16);
+
+    let joint_data = interleave_joint(&iris, &mask).unwrap();
+    let mask_bytes = mask.iter().map(|bit| *bit as u8).collect_vec();
+    assert_eq!(
+        joint
+            .layout
+            .extract_channel(&joint_data, JointChannel::Iris),
+        iris
+    );
+    assert_eq!(
+        joint
+            .layout
+            .extract_channel(&joint_data, JointChannel::Mask),
+        mask_bytes
+    );
+
+    for (channel, channel_data) in [
+        (JointChannel::Iris, &iris),
+        (JointChannel::Mask, &mask_bytes),
+    ] {
+        let channel_point = (0..joint.layout.num_channel_vars())
+            .map(|_| Bn256Scalar::rand(&mut rng))
+            .collect_vec();
+        // --- The MLE of the channel on its own ---
+        let evaluation = channel_data
+            .iter()
+            .zip(tensor_expand(&channel_point))
+            .fold(Bn256Scalar::zero(), |acc, (element, weight)| {
+                acc + Bn256Scalar::from(*element as u64) * weight
+            });
+        let point = joint.layout.joint_point(channel, &channel_point).unwrap();
+        let proof = prove_evaluation(
+            &joint_data,
+            &joint.output.blinding_factors,
+            &point,
+            &committer,
+        )
+        .unwrap();
+        assert!(verify_evaluation(
+            &joint.output.commitment,
+            &point,
+            evaluation,
+            &proof,
+            &committer
+        ));
+        // --- A wrong claimed evaluation is rejected ---
+        assert!(!verify_evaluation(
+            &joint.output.commitment,
+            &point,
+            evaluation + Bn256Scalar::from(1u64),
+            &proof,
+            &committer
+        ));
+    }
+
+    assert_eq!(
+        commit_joint(&iris, &mask[1..], &committer, test_seed("joint").bytes()).err(),
+        Some(HyraxError::ChannelLengthMismatch {
+            iris_len: 100,
+            mask_len: 99
+        })
+    );
+
+    // --- A layout too short to fill a row, and a channel point of the wrong length ---
+    let narrow = JointCommitmentLayout {
+        channel_len: 4,
+        log_num_cols: 4,
+    };
+    assert_eq!(
+        narrow.joint_point(JointChannel::Iris, &[Bn256Scalar::zero(); 2]),
+        Err(HyraxError::InvalidJointLayout {
+            log_num_cols: 4,
+            num_joint_vars: 3
+        })
+    );
+    assert_eq!(
+        joint
+            .layout
+            .joint_point(JointChannel::Iris, &[Bn256Scalar::zero(); 3]),
+        Err(HyraxError::InvalidEvaluationPointLength {
+            point_len: 3,
+            expected: 7
+        })
+    );
+}
+
+#[test]
+fn test_opening_parts_on_small_matrix() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        combine_row_commitments, compute_commitments, prove_evaluation, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- A 4 x 4 matrix with entries M[row][col] = 4 * row + col + 1 ---
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let data: Vec<u8> = (1..=16).collect();
+    let output =
+        compute_commitments(&data, &committer, test_seed("opening parts").bytes()).unwrap();
+
+    let [r0, r1, c0, c1] = [2u64, 3, 5, 7].map(Bn256Scalar::from);
+    let point = [r0, r1, c0, c1];
+    let proof = prove_evaluation(&data, &output.blinding_factors, &point, &committer).unwrap();
+
+    // --- The MLE of the matrix, evaluated directly: multilinear in each of the four bits ---
+    let one = Bn256Scalar::from(1u64);
+    let weight = |bit: u8, coordinate: Bn256Scalar| {
+        if bit == 1 {
+            coordinate
+        } else {
+            one - coordinate
+        }
+    };
+    let direct = data
+        .iter()
+        .enumerate()
+        .fold(Bn256Scalar::from(0u64), |acc, (idx, element)| {
+            let (row, col) = ((idx / 4) as u8, (idx % 4) as u8);
+            acc + Bn256Scalar::from(*element as u64)
+                * weight(row & 1, r0)
+                * weight(row >> 1, r1)
+                * weight(col & 1, c0)
+                * weight(col >> 1, c1)
+        });
+    assert_eq!(proof.evaluation(&[c0, c1]), direct);
+
+    // --- The combined row commitment commits to the revealed row combination ---
+    assert_eq!(
+        combine_row_commitments(&output.commitment, &[r0, r1]),
+        Bn256Point::msm(&proof.row_combination, &committer.generators)
+            + committer.blinding_generator * proof.combined_blinding_factor
+    );
+}
+
+#[test]
+fn test_verify_commitment() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{compute_commitments, verify_commitment, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(32, PUBLIC_STRING);
+    // --- 200 bytes, padded to 256, i.e. 8 rows ---
+    let data: Vec<u8> = (0..200).map(|i| (i * 3 % 256) as u8).collect();
+    let output = compute_commitments(&data, &committer, test_seed("verify").bytes()).unwrap();
+    let (commitment, blinding_factors) = (&output.commitment, &output.blinding_factors);
+    assert!(verify_commitment(
+        &data,
+        commitment,
+        blinding_factors,
+        &committer
+    ));
+
+    let mut flipped = data.clone();
+    flipped[150] ^= 1;
+    assert!(!verify_commitment(
+        &flipped,
+        commitment,
+        blinding_factors,
+        &committer
+    ));
+
+    // --- Explicit zero padding is the same data; dropping rows or blinding factors is not ---
+    let mut padded = data.clone();
+    padded.resize(256, 0);
+    assert!(verify_commitment(
+        &padded,
+        commitment,
+        blinding_factors,
+        &committer
+    ));
+    assert!(!verify_commitment(
+        &data[..100],
+        commitment,
+        blinding_factors,
+        &committer
+    ));
+    assert!(!verify_commitment(
+        &data,
+        &commitment[1..],
+        blinding_factors,
+        &committer
+    ));
+    assert!(!verify_commitment(
+        &data,
+        commitment,
+        &blinding_factors[1..],
+        &committer
+    ));
+    assert!(!verify_commitment(
+        &data[..10],
+        commitment,
+        blinding_factors,
+        &committer
+    ));
+}
+
+#[test]
+fn test_validate_proof_size() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, prove_evaluation, validate_proof_size, MAX_INPUT_LEN,
+        OPENING_PROOF_HEADER_LEN, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data: Vec<u8> = (0..128).collect();
+    let output = compute_commitments(&data, &committer, test_seed("proof size").bytes()).unwrap();
+    let point: Vec<Bn256Scalar> = (0..7).map(|i| Bn256Scalar::from(i as u64)).collect();
+    let proof = prove_evaluation(&data, &output.blinding_factors, &point, &committer).unwrap();
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(
+        validate_proof_size::<Bn256Point>(&proof_bytes, 8, 4),
+        Ok(())
+    );
+
+    // --- A proof claiming far more elements than 16 columns allow ---
+    let oversized = vec![0u8; 1000 * Bn256Point::SCALAR_ELEM_BYTEWIDTH];
+    assert_eq!(
+        validate_proof_size::<Bn256Point>(&oversized, 8, 4),
+        Err(HyraxError::InvalidByteLength {
+            expected: OPENING_PROOF_HEADER_LEN + 17 * Bn256Point::SCALAR_ELEM_BYTEWIDTH,
+            got: oversized.len()
+        })
+    );
+    assert_eq!(
+        validate_proof_size::<Bn256Point>(&proof_bytes, 6, 4),
+        Err(HyraxError::NonPowerOfTwoRows(6))
+    );
+    // --- Absurd dimensions are rejected without overflowing ---
+    assert_eq!(
+        validate_proof_size::<Bn256Point>(&proof_bytes, 1 << 20, 20),
+        Err(HyraxError::InputTooLarge {
+            len: 1 << 40,
+            max: MAX_INPUT_LEN
+        })
+    );
+    assert!(matches!(
+        validate_proof_size::<Bn256Point>(&proof_bytes, 8, 200),
+        Err(HyraxError::InputTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_binary_outputs_in_both_point_encodings() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs, compute_commitments_binary_outputs_with_encoding,
+        deserialize_commitment_from_bytes_compressed_concrete,
+        deserialize_commitment_from_bytes_uncompressed_concrete, HyraxCommitmentOutputSerialized,
+        PointEncoding,
+    };
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let data: Vec<u8> = (0..(1 << 14)).map(|i| (i * 7 % 256) as u8).collect();
+    let seed = test_seed("point encodings");
+    let compressed =
+        compute_commitments_binary_outputs_with_encoding(&data, seed, PointEncoding::Compressed)
+            .unwrap();
+    let uncompressed =
+        compute_commitments_binary_outputs_with_encoding(&data, seed, PointEncoding::Uncompressed)
+            .unwrap();
+    // --- The default is the compressed encoding ---
+    let default = compute_commitments_binary_outputs(&data, seed).unwrap();
+    assert_eq!(default.point_encoding, PointEncoding::Compressed);
+    assert_eq!(
+        default.commitment_serialized,
+        compressed.commitment_serialized
+    );
+
+    assert_eq!(
+        uncompressed.commitment_serialized.len(),
+        32 * Bn256Point::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH
+    );
+    assert_eq!(
+        uncompressed.blinding_factors_serialized,
+        compressed.blinding_factors_serialized
+    );
+    let from_compressed =
+        deserialize_commitment_from_bytes_compressed_concrete(&compressed.commitment_serialized)
+            .unwrap();
+    let from_uncompressed = deserialize_commitment_from_bytes_uncompressed_concrete(
+        &uncompressed.commitment_serialized,
+    )
+    .unwrap();
+    assert_eq!(from_compressed, from_uncompressed);
+    assert_eq!(
+        compressed.deserialize_commitment().unwrap(),
+        from_compressed
+    );
+    assert_eq!(
+        uncompressed.deserialize_commitment().unwrap(),
+        from_compressed
+    );
+
+    // --- The recorded encoding survives serde, and is Compressed when absent ---
+    let json = serde_json::to_string(&uncompressed).unwrap();
+    let decoded: HyraxCommitmentOutputSerialized = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.point_encoding, PointEncoding::Uncompressed);
+    let legacy = r#"{"commitment_serialized":[],"blinding_factors_serialized":[]}"#;
+    let decoded: HyraxCommitmentOutputSerialized = serde_json::from_str(legacy).unwrap();
+    assert_eq!(decoded.point_encoding, PointEncoding::Compressed);
+
+    // --- Uncompressed bytes do not parse as compressed ones ---
+    assert!(matches!(
+        deserialize_commitment_from_bytes_compressed_concrete(&uncompressed.commitment_serialized),
+        Err(HyraxError::InvalidCommitmentLength { .. })
+    ));
+}
+
+#[test]
+fn test_commitment_as_g1_affine() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{commitment_as_g1_affine, compute_commitments, PUBLIC_STRING};
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ec::AffineRepr;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data: Vec<u8> = (0..64).map(|i| i * 3).collect();
+    let mut commitment = compute_commitments(&data, &committer, test_seed("g1 affine").bytes())
+        .unwrap()
+        .commitment;
+    commitment.push(<Bn256Point as PrimeOrderCurve>::zero());
+
+    let affine = commitment_as_g1_affine(&commitment);
+    assert_eq!(affine.len(), commitment.len());
+    for (affine_point, point) in affine.iter().zip(commitment.iter()) {
+        assert!(affine_point.is_on_curve());
+        assert!(affine_point.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(Bn256Point::from(*affine_point), *point);
+    }
+    assert!(affine.last().unwrap().is_zero());
+}
+
+#[test]
+fn test_compute_commitments_safe_reseeds_trivial_rows() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_safe, reseed_trivial_rows, reseeded_row_blinding,
+        verify_commitment, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::Zero;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    // --- Row 2 of 4 is all zeros ---
+    let mut data: Vec<u8> = (1..=64).collect();
+    data[32..48].fill(0);
+    let seed = test_seed("reseed").bytes();
+
+    // --- With the seeded blinding factors nothing is trivial, so nothing changes ---
+    let safe = compute_commitments_safe(&data, &committer, seed).unwrap();
+    let plain = compute_commitments(&data, &committer, seed).unwrap();
+    assert!(safe.reseeded_rows.is_empty());
+    assert_eq!(safe.output.commitment, plain.commitment);
+
+    // --- Force the zero row's blinding factor to zero ---
+    let mut forced = plain;
+    forced.blinding_factors[2] = Bn256Scalar::zero();
+    forced.commitment[2] = committer.vector_commit(&data[32..48], &Bn256Scalar::zero());
+    assert_eq!(
+        forced.commitment[2],
+        <Bn256Point as PrimeOrderCurve>::zero()
+    );
+
+    // --- An output that does not match the data is rejected ---
+    let mut truncated = compute_commitments(&data, &committer, seed).unwrap();
+    truncated.commitment.pop();
+    assert_eq!(
+        reseed_trivial_rows(&data, truncated, &committer, seed).err(),
+        Some(HyraxError::RowCountMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+    let mut truncated = compute_commitments(&data, &committer, seed).unwrap();
+    truncated.blinding_factors.pop();
+    assert_eq!(
+        reseed_trivial_rows(&data, truncated, &committer, seed).err(),
+        Some(HyraxError::BlindingFactorCountMismatch {
+            expected: 4,
+            actual: 3
+        })
+    );
+
+    let repaired = reseed_trivial_rows(&data, forced, &committer, seed).unwrap();
+    assert_eq!(repaired.reseeded_rows, vec![(2, 1)]);
+    assert!(repaired
+        .output
+        .commitment
+        .iter()
+        .all(|row| *row != <Bn256Point as PrimeOrderCurve>::zero()));
+    assert_eq!(
+        repaired.output.blinding_factors[2],
+        reseeded_row_blinding::<Bn256Point>(seed, 2, 1)
+    );
+    assert_eq!(repaired.output.commitment[0], safe.output.commitment[0]);
+    assert!(verify_commitment(
+        &data,
+        &repaired.output.commitment,
+        &repaired.output.blinding_factors,
+        &committer
+    ));
+}
+
+#[test]
+fn test_opening_proof_bytes_round_trip() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, opening_proof_byte_len, prove_evaluation, verify_evaluation,
+        OpeningProof, OPENING_PROOF_VERSION, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+    use ark_ff::{BigInteger, PrimeField};
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data: Vec<u8> = (0..128).map(|i| (i * 7) as u8).collect();
+    let output = compute_commitments(&data, &committer, test_seed("proof bytes").bytes()).unwrap();
+    let point: Vec<Bn256Scalar> = (0..7).map(|i| Bn256Scalar::from(3 + i as u64)).collect();
+    let proof = prove_evaluation(&data, &output.blinding_factors, &point, &committer).unwrap();
+    let claimed_evaluation = proof.evaluation(&point[3..]);
+
+    // --- Serialize, deserialize, and verify the deserialized proof ---
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(proof_bytes[..2], [OPENING_PROOF_VERSION, 4]);
+    assert_eq!(
+        Some(proof_bytes.len()),
+        opening_proof_byte_len::<Bn256Point>(4)
+    );
+    let decoded = OpeningProof::<Bn256Point>::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(decoded, proof);
+    assert!(verify_evaluation(
+        &output.commitment,
+        &point,
+        claimed_evaluation,
+        &decoded,
+        &committer
+    ));
+
+    // --- Unknown versions ---
+    let mut wrong_version = proof_bytes.clone();
+    wrong_version[0] = OPENING_PROOF_VERSION + 1;
+    assert!(matches!(
+        OpeningProof::<Bn256Point>::from_bytes(&wrong_version),
+        Err(HyraxError::DeserializationError(_))
+    ));
+
+    // --- Lengths that do not match the header ---
+    for bytes in [
+        &proof_bytes[..1],
+        &proof_bytes[..proof_bytes.len() - 1],
+        &[proof_bytes.as_slice(), &[0u8; 32]].concat(),
+    ] {
+        assert!(matches!(
+            OpeningProof::<Bn256Point>::from_bytes(bytes),
+            Err(HyraxError::InvalidByteLength { .. })
+        ));
+    }
+    let mut wrong_log_num_cols = proof_bytes.clone();
+    wrong_log_num_cols[1] = 200;
+    assert!(OpeningProof::<Bn256Point>::from_bytes(&wrong_log_num_cols).is_err());
+
+    // --- Unreduced scalars ---
+    let mut unreduced = proof_bytes.clone();
+    unreduced[2..2 + Bn256Point::SCALAR_ELEM_BYTEWIDTH]
+        .copy_from_slice(&Bn256Scalar::MODULUS.to_bytes_le());
+    assert_eq!(
+        OpeningProof::<Bn256Point>::from_bytes(&unreduced),
+        Err(HyraxError::NonCanonicalCoordinate)
+    );
+}
+
+#[test]
+fn test_committer_stream_matches_compute_commitments() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_from_iter, HyraxCommitterStream, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let seed = test_seed("stream").bytes();
+    // --- 100 bytes are padded to 128, i.e. 6 full rows, a partial one and a row of zeros ---
+    let data: Vec<u8> = (0..100).map(|i| (i * 13 + 1) as u8).collect();
+    let expected = compute_commitments(&data, &committer, seed).unwrap();
+
+    for chunk_len in [1, 7, 16, 33, 100] {
+        let mut stream = HyraxCommitterStream::new(&committer, seed).unwrap();
+        let mut emitted = vec![];
+        for chunk in data.chunks(chunk_len) {
+            emitted.extend_from_slice(stream.update(chunk).unwrap());
+        }
+        assert_eq!(emitted, expected.commitment[..6]);
+        let output = stream.finalize();
+        assert_eq!(output.commitment, expected.commitment);
+        assert_eq!(output.blinding_factors, expected.blinding_factors);
+    }
+
+    // --- Less data than a row gives no rows, as for compute_commitments ---
+    let mut stream = HyraxCommitterStream::new(&committer, seed).unwrap();
+    assert!(stream.update(&data[..5]).unwrap().is_empty());
+    assert_eq!(
+        stream.finalize().commitment,
+        compute_commitments(&data[..5], &committer, seed)
+            .unwrap()
+            .commitment
+    );
+
+    // --- A committer without generators is rejected, rather than never filling a row ---
+    let (empty, _) = committer.split_at(0);
+    assert_eq!(
+        HyraxCommitterStream::new(&empty, seed).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 0,
+            n_cols: 0
+        })
+    );
+    assert_eq!(
+        compute_commitments_from_iter(data.iter().copied(), data.len(), &empty, seed).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 0,
+            n_cols: 0
+        })
+    );
+}
+
+#[test]
+fn test_compute_commitments_from_iter() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_from_iter, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let seed = test_seed("from iter").bytes();
+    let bytes = || (0..200_u32).map(|i| (i * 31 % 251) as u8);
+    let data = bytes().collect::<Vec<u8>>();
+    let expected = compute_commitments(&data, &committer, seed).unwrap();
+    let output = compute_commitments_from_iter(bytes(), data.len(), &committer, seed).unwrap();
+    assert_eq!(output.commitment, expected.commitment);
+    assert_eq!(output.blinding_factors, expected.blinding_factors);
+
+    // --- The iterator must yield exactly total_len bytes ---
+    assert_eq!(
+        compute_commitments_from_iter(bytes(), 201, &committer, seed).err(),
+        Some(HyraxError::InvalidByteLength {
+            expected: 201,
+            got: 200
+        })
+    );
+    assert_eq!(
+        compute_commitments_from_iter(bytes(), 150, &committer, seed).err(),
+        Some(HyraxError::InvalidByteLength {
+            expected: 150,
+            got: 151
+        })
+    );
+    assert!(compute_commitments_from_iter(std::iter::repeat(0), 64, &committer, seed).is_err());
+}
+
+#[test]
+fn test_verify_commitment_opening() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{compute_commitments_binary_outputs, verify_commitment_opening};
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- 1500 bytes are padded to 4 rows of 512, the last one partial ---
+    let data: Vec<u8> = (0..1500).map(|i| (i * 7 % 256) as u8).collect();
+    let serialized =
+        compute_commitments_binary_outputs(&data, test_seed("verify opening")).unwrap();
+    let commitment = &serialized.commitment_serialized;
+    let blinding_factors = &serialized.blinding_factors_serialized;
+    assert_eq!(
+        verify_commitment_opening(&data, commitment, blinding_factors),
+        Ok(())
+    );
+
+    // --- A single flipped byte in the image, here in the partial last row ---
+    let mut flipped = data.clone();
+    flipped[1400] ^= 1;
+    assert_eq!(
+        verify_commitment_opening(&flipped, commitment, blinding_factors),
+        Err(HyraxError::RowCommitmentMismatch(2))
+    );
+
+    // --- Swapped blinding factors ---
+    let width = Bn256Point::SCALAR_ELEM_BYTEWIDTH;
+    let mut swapped = blinding_factors.clone();
+    swapped[..width].copy_from_slice(&blinding_factors[width..2 * width]);
+    swapped[width..2 * width].copy_from_slice(&blinding_factors[..width]);
+    assert_eq!(
+        verify_commitment_opening(&data, commitment, &swapped),
+        Err(HyraxError::RowCommitmentMismatch(0))
+    );
+
+    // --- Data with a different number of rows ---
+    assert_eq!(
+        verify_commitment_opening(&data[..1000], commitment, blinding_factors),
+        Err(HyraxError::RowCountMismatch {
+            expected: 2,
+            actual: 4
+        })
+    );
+}
+
+#[test]
+fn test_verify_commitment_opening_with_committer() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs_with_encoding, verify_commitment_opening_with_committer,
+        PointEncoding, LOG_NUM_COLS, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let data: Vec<u8> = (0..1500).map(|i| (i * 11 % 256) as u8).collect();
+    let vector_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+
+    // --- Both encodings verify under their recorded `point_encoding` ---
+    for point_encoding in [PointEncoding::Compressed, PointEncoding::Uncompressed] {
+        let serialized = compute_commitments_binary_outputs_with_encoding(
+            &data,
+            test_seed("test_verify_commitment_opening_with_committer"),
+            point_encoding,
+        )
+        .unwrap();
+        assert_eq!(
+            verify_commitment_opening_with_committer(
+                &data,
+                &serialized.commitment_serialized,
+                &serialized.blinding_factors_serialized,
+                serialized.point_encoding,
+                &vector_committer,
+            ),
+            Ok(())
+        );
+    }
+
+    // --- A committer with a different number of columns is a shape mismatch ---
+    let serialized = compute_commitments_binary_outputs_with_encoding(
+        &data,
+        test_seed("test_verify_commitment_opening_with_committer"),
+        PointEncoding::Compressed,
+    )
+    .unwrap();
+    let narrow_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(256, PUBLIC_STRING);
+    assert_eq!(
+        verify_commitment_opening_with_committer(
+            &data,
+            &serialized.commitment_serialized,
+            &serialized.blinding_factors_serialized,
+            PointEncoding::Compressed,
+            &narrow_committer,
+        ),
+        Err(HyraxError::RowCountMismatch {
+            expected: 8,
+            actual: 4
+        })
+    );
+}
+
+#[test]
+fn test_combine_commitments_is_homomorphic() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        combine_blinding_factors, combine_commitments, compute_commitments, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    // --- A 4 x 8 matrix ---
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let data: Vec<u8> = (0..32).map(|i| (i * 29 % 256) as u8).collect();
+    let output = compute_commitments(&data, &committer, test_seed("combine").bytes()).unwrap();
+    let coeffs: Vec<Bn256Scalar> = [3u64, 1 << 40, 0, 12345]
+        .iter()
+        .map(|coeff| Bn256Scalar::from(*coeff))
+        .collect();
+
+    let mut combined_row = vec![Bn256Scalar::from(0u64); 8];
+    for (row, coeff) in data.chunks(8).zip(coeffs.iter()) {
+        for (acc, element) in combined_row.iter_mut().zip(row.iter()) {
+            *acc += *coeff * Bn256Scalar::from(*element as u64);
+        }
+    }
+    let combined_blinding = combine_blinding_factors(&output.blinding_factors, &coeffs);
+    assert_eq!(
+        combine_commitments(&output.commitment, &coeffs),
+        committer.scalar_vector_commit(&combined_row, &combined_blinding)
+    );
+}
+
+#[test]
+fn test_commitment_output_serde_json_round_trip() {
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments, compute_commitments_binary_outputs_with_shape, HyraxCommitmentOutput,
+        HyraxCommitmentOutputSerialized, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data: Vec<u8> = (0..64).map(|i| (i * 37 % 256) as u8).collect();
+    let seed = test_seed("serde json").bytes();
+    let output = compute_commitments(&data, &committer, seed).unwrap();
+
+    let json = serde_json::to_string(&output).unwrap();
+    let decoded: HyraxCommitmentOutput<Bn256Point> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.commitment, output.commitment);
+    assert_eq!(decoded.blinding_factors, output.blinding_factors);
+
+    // --- The byte fields are those of the binary outputs ---
+    let serialized: HyraxCommitmentOutputSerialized = serde_json::from_str(&json).unwrap();
+    let binary_outputs = compute_commitments_binary_outputs_with_shape(&data, seed, 4).unwrap();
+    assert_eq!(
+        serialized.commitment_serialized,
+        binary_outputs.commitment_serialized
+    );
+    assert_eq!(
+        serialized.blinding_factors_serialized,
+        binary_outputs.blinding_factors_serialized
+    );
+
+    // --- Mismatched row counts are rejected ---
+    let truncated = HyraxCommitmentOutputSerialized {
+        blinding_factors_serialized: serialized.blinding_factors_serialized[32..].to_vec(),
+        ..serialized
+    };
+    let json = serde_json::to_string(&truncated).unwrap();
+    assert!(serde_json::from_str::<HyraxCommitmentOutput<Bn256Point>>(&json).is_err());
+}
+
+#[test]
+fn test_binary_outputs_with_committer() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs, compute_commitments_binary_outputs_with_committer,
+        LOG_NUM_COLS, PUBLIC_STRING,
+    };
+    use crate::pedersen::PedersenCommitter;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let loaded: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::from_bytes(&committer.to_bytes()).unwrap();
+    let data: Vec<u8> = (0..(1 << 12)).map(|i| (i * 13 % 256) as u8).collect();
+    let seed = test_seed("with committer");
+
+    let output = compute_commitments_binary_outputs_with_committer(&data, seed, &loaded).unwrap();
+    let expected = compute_commitments_binary_outputs(&data, seed).unwrap();
+    assert_eq!(output.commitment_serialized, expected.commitment_serialized);
+    assert_eq!(
+        output.blinding_factors_serialized,
+        expected.blinding_factors_serialized
+    );
+
+    // --- The data must fill a whole number of rows ---
+    let three_columns: PedersenCommitter<Bn256Point> = PedersenCommitter::new(3, PUBLIC_STRING);
+    assert_eq!(
+        compute_commitments_binary_outputs_with_committer(&data, seed, &three_columns).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 1 << 12,
+            n_cols: 3
+        })
+    );
+}
+
+#[test]
+fn test_compute_commitments_bounded() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::{
+        commitment_memory_bytes, compute_commitments, compute_commitments_bounded, PUBLIC_STRING,
+    };
+    use crate::pedersen::{PedersenCommitter, U8_BITWIDTH};
+    use ark_bn254::Fr as Bn256Scalar;
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let data: Vec<u8> = (0..100).map(|i| (i * 41 % 256) as u8).collect();
+    let seed = test_seed("bounded").bytes();
+
+    // --- 17 generators and 16 * 8 doublings, one row, and 8 padded rows of output, at their
+    // in-memory sizes (96 bytes for a projective point, not its 65-byte encoding) ---
+    let required = commitment_memory_bytes(data.len(), &committer).unwrap();
+    let point_size = std::mem::size_of::<Bn256Point>();
+    assert_eq!(point_size, 96);
+    assert_eq!(
+        required,
+        (17 + 16 * U8_BITWIDTH) * point_size
+            + 16
+            + 8 * (point_size + std::mem::size_of::<Bn256Scalar>())
+    );
+
+    assert_eq!(
+        compute_commitments_bounded(&data, &committer, seed, 1024).err(),
+        Some(HyraxError::MemoryBudgetExceeded {
+            required,
+            budget: 1024
+        })
+    );
+    assert!(compute_commitments_bounded(&data, &committer, seed, required - 1).is_err());
+
+    let output = compute_commitments_bounded(&data, &committer, seed, 1 << 20).unwrap();
+    let expected = compute_commitments(&data, &committer, seed).unwrap();
+    assert_eq!(output.commitment, expected.commitment);
+    assert_eq!(output.blinding_factors, expected.blinding_factors);
+
+    // --- A committer without generators has no rows to commit to ---
+    let empty: PedersenCommitter<Bn256Point> = PedersenCommitter::new(0, PUBLIC_STRING);
+    assert_eq!(
+        compute_commitments_bounded(&data, &empty, seed, 1 << 20).err(),
+        Some(HyraxError::InvalidMatrixShape {
+            data_len: 128,
+            n_cols: 0
+        })
+    );
+}
+
+#[test]
+fn test_deserialize_commitment_with_hints() {
+    use crate::curves::PrimeOrderCurve;
+    use crate::entropy::test_seed;
+    use crate::iriscode_commit::{
+        compute_commitments_binary_outputs_with_shape,
+        deserialize_commitment_from_bytes_compressed, deserialize_commitment_with_hints,
+        generate_hints, CommitmentHints,
+    };
+    use ark_bn254::G1Projective as Bn256Point;
+
+    let data: Vec<u8> = (0..(1 << 10)).map(|i| (i * 17 % 256) as u8).collect();
+    let commitment_bytes =
+        compute_commitments_binary_outputs_with_shape(&data, test_seed("hints"), 6)
+            .unwrap()
+            .commitment_serialized;
+    let expected =
+        deserialize_commitment_from_bytes_compressed::<Bn256Point>(&commitment_bytes).unwrap();
+    let hints = generate_hints::<Bn256Point>(&commitment_bytes).unwrap();
+    assert_eq!(hints.y_coordinates.len(), 16 * 32);
+    assert_eq!(
+        deserialize_commitment_with_hints::<Bn256Point>(&commitment_bytes, Some(&hints)).unwrap(),
+        expected
+    );
+
+    // --- Missing or misshapen hints fall back to full decompression ---
+    assert_eq!(
+        deserialize_commitment_with_hints::<Bn256Point>(&commitment_bytes, None).unwrap(),
+        expected
+    );
+    let truncated = CommitmentHints {
+        y_coordinates: hints.y_coordinates[1..].to_vec(),
+    };
+    assert_eq!(
+        deserialize_commitment_with_hints::<Bn256Point>(&commitment_bytes, Some(&truncated))
+            .unwrap(),
+        expected
+    );
+
+    // --- Forged hints: an off-curve y, and the other root (on the curve, but the negated
+    // point) cannot change the result ---
+    let mut forged = hints.clone();
+    forged.y_coordinates[0] ^= 1;
+    let negated_y = (-expected[1]).to_bytes_uncompressed()[33..].to_vec();
+    forged.y_coordinates[32..64].copy_from_slice(&negated_y);
+    assert_eq!(
+        deserialize_commitment_with_hints::<Bn256Point>(&commitment_bytes, Some(&forged)).unwrap(),
+        expected
+    );
+
+    // --- Malformed commitment bytes still error ---
+    let mut bad_x = commitment_bytes.clone();
+    bad_x[1..33].copy_from_slice(&[0xff; 32]);
+    assert!(deserialize_commitment_with_hints::<Bn256Point>(&bad_x, Some(&hints)).is_err());
+}
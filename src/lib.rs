@@ -1,4 +1,11 @@
 pub mod curves;
+pub mod entropy;
+pub mod error;
 pub mod iriscode_commit;
 pub mod pedersen;
+pub mod row_commit_cache;
+pub mod scalar_serde;
+pub mod setup;
+#[cfg(feature = "rayon")]
+pub mod soak;
 pub mod utils;
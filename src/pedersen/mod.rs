@@ -1,24 +1,124 @@
 use crate::utils::Sha3XofReaderWrapper;
 
 use super::curves::PrimeOrderCurve;
+use crate::error::HyraxError;
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand};
 use num_traits::PrimInt;
+use rand::{Rng, RngCore};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use sha3::digest::ExtendableOutput;
 use sha3::digest::Input;
 use sha3::Shake256;
+use std::collections::HashSet;
 
 #[cfg(test)]
 pub mod tests;
 
 /// For committing to vectors of bytes (u8s) using the Pedersen commitment scheme.
+///
+/// The generators can be serialized in three formats, none of which includes the precomputed
+/// tables:
+/// + `to_bytes`/`from_bytes`: the blinding generator, then the message generators, each
+///     compressed. This is the layout of a `SetupManifest`'s `generator_bytes`, and the one to use
+///     to persist a whole committer.
+/// + `to_generators_bytes`/`from_generators_bytes`: the message generators only, compressed, i.e.
+///     `to_bytes` without its first point, for applications that supply the blinding generator
+///     separately.
+/// + `generators_to_evm_registry_bytes`/`generators_from_evm_registry_bytes`: the points of
+///     `to_bytes`, in the same order, in the EVM precompile format (uncompressed, big endian
+///     coordinates, no flags), for on-chain registries.
 pub struct PedersenCommitter<C: PrimeOrderCurve> {
     /// vector of "g" generators, i.e. the generators that are exponentiated by the message elements themselves (length > 0)
     pub generators: Vec<C>,
     /// the "h" generator which is exponentiated by the blinding factor
     pub blinding_generator: C,
     generator_doublings: Vec<Vec<C>>,
+    /// the order in which each generator's doublings are laid out in `generator_doublings`
+    doublings_bit_order: BitOrder,
+    /// fixed-base comb tables for scalar field messages, see `PedersenCommitter::precompute_comb`
+    comb_tables: Option<CombTables<C>>,
 }
 
-const U8_BITWIDTH: usize = 8;
+// The comb tables of all generators for a given window size: splitting a scalar's bits into
+// `window_bits` rows of `num_columns` bits each, `tables[i][b - 1]` is
+// `sum_{j : bit j of b is set} 2^(j * num_columns) * generators[i]`, for b in 1..2^window_bits.
+struct CombTables<C: PrimeOrderCurve> {
+    window_bits: usize,
+    num_columns: usize,
+    tables: Vec<Vec<C>>,
+}
+
+/// Fixed-base window tables for `PedersenCommitter::vector_commit_windowed`, built by
+/// `PedersenCommitter::build_window_table`. Unlike the comb tables, they are held apart from the
+/// committer, so that a prover can build them once at setup without rebuilding the committer.
+/// `tables[i][d - 1]` is `d * generators[i]`, for d in 1..2^window_bits.
+pub struct WindowTable<C: PrimeOrderCurve> {
+    window_bits: usize,
+    tables: Vec<Vec<C>>,
+}
+
+impl<C: PrimeOrderCurve> WindowTable<C> {
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// The number of generators the table covers.
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+}
+
+/// The order of the bits in a binary decomposition (and of the matching doublings table), see
+/// `PedersenCommitter::from_generators_with_bit_order`.
+/// The order only affects how the doublings tables are laid out and read: messages of at least
+/// `MSM_THRESHOLD` elements (e.g. every row of the Orb's matrix) are committed to with the MSM,
+/// which does not use the tables, and every order gives the same commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Least significant bit first, i.e. bit `i` has weight `2^i`.
+    LittleEndian,
+    /// Most significant bit first.
+    BigEndian,
+}
+
+/// Messages of at least this many elements are committed to with a multi-scalar multiplication
+/// rather than by summing precomputed doublings, see `PedersenCommitter::vector_commit`. This
+/// covers every row of the Orb's 512-column matrix, for which the MSM is about 3x faster.
+/// (The bucketed MSM has a fixed cost of ~2^(U8_BITWIDTH + 1) additions, which only pays off
+/// for longer messages; on BN254 the crossover is at around 90 elements.)
+pub const MSM_THRESHOLD: usize = 96;
+
+/// A single step of a traced commitment (see `PedersenCommitter::commit_with_trace`).
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceOperation<C: PrimeOrderCurve> {
+    /// Added the precomputed doublings `2^i * generators[generator_index]` for each set `bits[i]`
+    /// (`bits` is little-endian unless the committer was built with
+    /// `PedersenCommitter::from_generators_with_bit_order` and `BitOrder::BigEndian`, in which
+    /// case `bits[i]` has weight `2^(U8_BITWIDTH - 1 - i)`).
+    MessageElement {
+        generator_index: usize,
+        bits: Vec<bool>,
+    },
+    /// Added `blinding_generator * blinding`.
+    Blinding { blinding: C::Scalar },
+}
+
+/// The ordered list of operations that produced a commitment, so that it can be replayed and
+/// independently verified step by step.
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitTrace<C: PrimeOrderCurve> {
+    pub operations: Vec<TraceOperation<C>>,
+}
+
+/// Bitwidth of the message elements, i.e. the depth of the precomputed doublings tables.
+pub const U8_BITWIDTH: usize = 8;
 impl<C: PrimeOrderCurve> PedersenCommitter<C> {
     /// Creates a new PedersenCommitter with random generators.  See also [PedersenCommitter].
     /// Generators are sampled using the public string and the Shake256 hash function.
@@ -30,28 +130,435 @@ impl<C: PrimeOrderCurve> PedersenCommitter<C> {
         let blinding_generator_h = all_generators[0];
         let generators_g_i = all_generators[1..].to_vec();
 
-        let generator_doublings: Vec<Vec<C>> = generators_g_i
-            .clone()
-            .into_iter()
-            .map(|gen| precompute_doublings(gen, U8_BITWIDTH))
+        Self::from_parts(generators_g_i, blinding_generator_h)
+    }
+
+    /// Creates a new PedersenCommitter with generators drawn from `rng` by `C::random`, the first
+    /// being the blinding generator, e.g. for tests that do not need a public string.
+    /// The identity is redrawn, since an identity message generator would silently drop its
+    /// element from the commitment (and an identity blinding generator would make it non-hiding).
+    /// Post: self.generators.len() == num_generators
+    pub fn random(num_generators: usize, rng: &mut impl RngCore) -> Self {
+        Self::from_non_identity_samples(num_generators, || C::random(&mut *rng))
+    }
+
+    // Same as `random`, drawing the points from `sample` (which the tests stub out, since no
+    // `C::random` of the crate's curves ever returns the identity).
+    fn from_non_identity_samples(num_generators: usize, mut sample: impl FnMut() -> C) -> Self {
+        let mut sample_non_identity = || loop {
+            let point = sample();
+            if point != C::zero() {
+                return point;
+            }
+        };
+        let blinding_generator = sample_non_identity();
+        let generators = (0..num_generators).map(|_| sample_non_identity()).collect();
+
+        Self::from_parts(generators, blinding_generator)
+    }
+
+    /// Same as `new`, but with doublings tables covering integers of up to `int_abs_val_bitwidth`
+    /// bits (and at least `U8_BITWIDTH`), so that `integer_vector_commit` commits to such
+    /// integers from the tables rather than by scalar multiplication.
+    /// Errors with `HyraxError::BitwidthExceedsField` unless
+    /// `int_abs_val_bitwidth < C::Scalar::MODULUS_BIT_SIZE`, since wider integers could exceed
+    /// the modulus and wrap around, making the commitment ambiguous.
+    pub fn new_with_bitwidth(
+        num_generators: usize,
+        public_string: &str,
+        int_abs_val_bitwidth: usize,
+    ) -> Result<Self, HyraxError> {
+        validate_bitwidth::<C>(int_abs_val_bitwidth)?;
+        let all_generators = Self::sample_generators(num_generators + 1, public_string);
+        Ok(Self::from_parts_with_layout(
+            all_generators[1..].to_vec(),
+            all_generators[0],
+            int_abs_val_bitwidth.max(U8_BITWIDTH),
+            BitOrder::LittleEndian,
+        ))
+    }
+
+    /// Creates a new PedersenCommitter from externally supplied generators, e.g. ones loaded from
+    /// a file, precomputing the doublings tables as `new` does.
+    pub fn from_generators(generators: Vec<C>, blinding_generator: C) -> Self {
+        Self::from_parts(generators, blinding_generator)
+    }
+
+    /// Same as `from_generators`, laying out the 8-bit doublings tables (and decomposing the
+    /// message elements) in `doublings_bit_order`, e.g. to match tables produced by a tool with
+    /// a most-significant-bit-first convention. The commitments are the same for every order.
+    pub fn from_generators_with_bit_order(
+        generators: Vec<C>,
+        blinding_generator: C,
+        doublings_bit_order: BitOrder,
+    ) -> Self {
+        Self::from_parts_with_bit_order(generators, blinding_generator, doublings_bit_order)
+    }
+
+    // Builds the committer from its generators, precomputing the doublings tables.
+    fn from_parts(generators: Vec<C>, blinding_generator: C) -> Self {
+        Self::from_parts_with_bit_order(generators, blinding_generator, BitOrder::LittleEndian)
+    }
+
+    // Same as `from_parts`, laying out the doublings tables in the given bit order.
+    fn from_parts_with_bit_order(
+        generators: Vec<C>,
+        blinding_generator: C,
+        doublings_bit_order: BitOrder,
+    ) -> Self {
+        Self::from_parts_with_layout(
+            generators,
+            blinding_generator,
+            U8_BITWIDTH,
+            doublings_bit_order,
+        )
+    }
+
+    // Same as `from_parts`, with doublings tables of `bitwidth` entries in the given bit order.
+    // Pre: bitwidth >= U8_BITWIDTH
+    fn from_parts_with_layout(
+        generators: Vec<C>,
+        blinding_generator: C,
+        bitwidth: usize,
+        doublings_bit_order: BitOrder,
+    ) -> Self {
+        // each generator's doublings chain is independent of the others', so under the `rayon`
+        // feature they are computed in parallel
+        #[cfg(feature = "rayon")]
+        let generator_iter = generators.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let generator_iter = generators.iter();
+        let generator_doublings: Vec<Vec<C>> = generator_iter
+            .map(|gen| precompute_doublings_with_bit_order(*gen, bitwidth, doublings_bit_order))
             .collect();
 
         Self {
-            generators: generators_g_i,
-            blinding_generator: blinding_generator_h,
+            generators,
+            blinding_generator,
             generator_doublings,
+            doublings_bit_order,
+            comb_tables: None,
+        }
+    }
+
+    /// Splits the committer into the committers for the first `mid` generators and for the rest,
+    /// e.g. to commit to the two halves of a row separately. Both halves keep the blinding
+    /// generator (and any precomputed tables of their generators), so by the additive homomorphism
+    /// the two halves' commitments with blindings `r1` and `r2` sum to the full commitment with
+    /// blinding `r1 + r2`. Splitting at 0 or at `self.generators.len()` gives an empty half.
+    /// Pre: mid <= self.generators.len()
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.generators.len());
+        let (left_generators, right_generators) = self.generators.split_at(mid);
+        let (left_doublings, right_doublings) = self.generator_doublings.split_at(mid);
+        let (left_comb_tables, right_comb_tables) = match &self.comb_tables {
+            Some(comb_tables) => {
+                let (left_tables, right_tables) = comb_tables.tables.split_at(mid);
+                let with_tables = |tables: &[Vec<C>]| CombTables {
+                    window_bits: comb_tables.window_bits,
+                    num_columns: comb_tables.num_columns,
+                    tables: tables.to_vec(),
+                };
+                (
+                    Some(with_tables(left_tables)),
+                    Some(with_tables(right_tables)),
+                )
+            }
+            None => (None, None),
+        };
+        let half = |generators: &[C], doublings: &[Vec<C>], comb_tables| Self {
+            generators: generators.to_vec(),
+            blinding_generator: self.blinding_generator,
+            generator_doublings: doublings.to_vec(),
+            doublings_bit_order: self.doublings_bit_order,
+            comb_tables,
+        };
+        (
+            half(left_generators, left_doublings, left_comb_tables),
+            half(right_generators, right_doublings, right_comb_tables),
+        )
+    }
+
+    /// Serializes the generators, the blinding generator first and then the message generators,
+    /// each as its `to_bytes_compressed` (the layout of a `SetupManifest`'s `generator_bytes`).
+    /// The precomputed tables are not serialized; `from_bytes` rebuilds the doublings tables.
+    /// Post: result.len() == (self.generators.len() + 1) * C::COMPRESSED_CURVE_POINT_BYTEWIDTH
+    pub fn to_bytes(&self) -> Vec<u8> {
+        points_to_bytes_compressed(
+            std::iter::once(&self.blinding_generator).chain(&self.generators),
+        )
+    }
+
+    /// Inverse of `to_bytes`, with the doublings tables of `new`, so that a committer can be
+    /// loaded instead of resampling its generators from the public string.
+    /// Errors with `HyraxError::InvalidByteLength` if the bytes are empty or not a whole number
+    /// of points, and with the decoding error of any malformed point.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HyraxError> {
+        let mut generators = points_from_bytes_compressed(bytes)?;
+        let blinding_generator = generators.remove(0);
+        Ok(Self::from_parts(generators, blinding_generator))
+    }
+
+    /// Serializes the message generators only, each as its `to_bytes_compressed`, for
+    /// `from_generators_bytes`.
+    /// Post: result.len() == self.generators.len() * C::COMPRESSED_CURVE_POINT_BYTEWIDTH
+    pub fn to_generators_bytes(&self) -> Vec<u8> {
+        points_to_bytes_compressed(&self.generators)
+    }
+
+    /// Inverse of `to_generators_bytes`, with the given blinding generator and the doublings
+    /// tables of `new_with_bitwidth`, so that an application can sample the generators once,
+    /// persist them, and reload them without resampling. For the generators and bitwidth of a
+    /// committer built from a public string, the result is identical to that committer.
+    /// Errors as `from_bytes` for malformed bytes, and as `new_with_bitwidth` for too wide an
+    /// `int_abs_val_bitwidth`.
+    pub fn from_generators_bytes(
+        bytes: &[u8],
+        blinding_generator: C,
+        int_abs_val_bitwidth: usize,
+    ) -> Result<Self, HyraxError> {
+        validate_bitwidth::<C>(int_abs_val_bitwidth)?;
+        Ok(Self::from_parts_with_layout(
+            points_from_bytes_compressed(bytes)?,
+            blinding_generator,
+            int_abs_val_bitwidth.max(U8_BITWIDTH),
+            BitOrder::LittleEndian,
+        ))
+    }
+
+    /// Checks that the generators (including the blinding generator) are those that `new`
+    /// samples from `public_string`, e.g. after loading a committer with `from_bytes`.
+    /// Pre: public_string.len() >= 32
+    pub fn matches_public_string(&self, public_string: &str) -> bool {
+        let all_generators = Self::sample_generators(self.generators.len() + 1, public_string);
+        all_generators[0] == self.blinding_generator && all_generators[1..] == self.generators[..]
+    }
+
+    /// Checks the nothing-up-my-sleeve property of the blinding generator: that it is the point
+    /// `new` samples first (at the reserved index 0) from `public_string`, so that nobody could
+    /// have chosen it with a known discrete log relation to the message generators, and that it
+    /// is not one of the message generators (which would break the hiding of the commitments).
+    /// Pre: public_string.len() >= 32
+    pub fn verify_blinding_generator_independence(&self, public_string: &str) -> bool {
+        Self::sample_generators(1, public_string)[0] == self.blinding_generator
+            && !self.generators.contains(&self.blinding_generator)
+    }
+
+    /// The number of bits of the integers the doublings tables cover: `U8_BITWIDTH`, or the
+    /// (larger) `int_abs_val_bitwidth` of `new_with_bitwidth` or `from_generators_bytes`.
+    /// A committer without message generators has no tables, and reports `U8_BITWIDTH`.
+    pub fn bitwidth(&self) -> usize {
+        self.generator_doublings
+            .first()
+            .map_or(U8_BITWIDTH, Vec::len)
+    }
+
+    /// The number of curve points the committer holds: its generators (including the blinding
+    /// generator) and their precomputed doublings and comb tables.
+    pub fn num_precomputed_points(&self) -> usize {
+        let num_doublings: usize = self.generator_doublings.iter().map(Vec::len).sum();
+        let num_comb_points: usize = self.comb_tables.as_ref().map_or(0, |comb_tables| {
+            comb_tables.tables.iter().map(Vec::len).sum()
+        });
+        self.generators.len() + 1 + num_doublings + num_comb_points
+    }
+
+    /// Precomputes fixed-base comb tables with the given window size for `vector_commit_comb`.
+    /// Each generator gets a table of `2^window_bits - 1` points, so the tables take
+    /// `generators.len() * (2^window_bits - 1)` points of memory (about 12 MiB for 512 BN254
+    /// generators with `window_bits = 8`, or 0.7 MiB with `window_bits = 4`).
+    /// In exchange, committing to a vector of full-width scalars costs only
+    /// `ceil(num_bits / window_bits)` doublings (shared across all generators) and at most as
+    /// many additions per element, where `num_bits` is the bit size of the scalar field.
+    /// Pre: 1 <= window_bits <= 16
+    pub fn precompute_comb(mut self, window_bits: usize) -> Self {
+        assert!((1..=16).contains(&window_bits));
+        let num_bits = C::Scalar::MODULUS_BIT_SIZE as usize;
+        let num_columns = num_bits.div_ceil(window_bits);
+        let tables = self
+            .generators
+            .iter()
+            .map(|generator| {
+                // the generator shifted to the start of each row of the comb
+                let row_bases = std::iter::successors(Some(*generator), |base| {
+                    Some((0..num_columns).fold(*base, |acc, _| acc.double()))
+                })
+                .take(window_bits)
+                .collect::<Vec<_>>();
+                let mut table = vec![C::zero(); 1 << window_bits];
+                for teeth in 1..(1_usize << window_bits) {
+                    let lowest_tooth = teeth.trailing_zeros() as usize;
+                    table[teeth] = table[teeth & (teeth - 1)] + row_bases[lowest_tooth];
+                }
+                table.remove(0);
+                table
+            })
+            .collect();
+        self.comb_tables = Some(CombTables {
+            window_bits,
+            num_columns,
+            tables,
+        });
+        self
+    }
+
+    /// Commits to the vector of scalars using the specified blinding factor and the comb tables
+    /// precomputed by `precompute_comb`.
+    /// Pre: the comb tables have been precomputed.
+    /// Pre: message.len() <= self.generators.len()
+    /// Post: same result as `sum_i message[i] * generators[i] + blinding * blinding_generator`.
+    pub fn vector_commit_comb(&self, message: &[C::Scalar], blinding: &C::Scalar) -> C {
+        assert!(message.len() <= self.generators.len());
+        let comb_tables = self
+            .comb_tables
+            .as_ref()
+            .expect("comb tables must be precomputed with `precompute_comb`");
+        // teeth[i][column]: the bits of message[i] in that column of the comb, as a table index
+        let teeth = message
+            .iter()
+            .map(|element| {
+                let bits = element.into_bigint().to_bits_le();
+                (0..comb_tables.num_columns)
+                    .map(|column| {
+                        (0..comb_tables.window_bits)
+                            .filter(|row| {
+                                bits.get(row * comb_tables.num_columns + column) == Some(&true)
+                            })
+                            .fold(0_usize, |acc, row| acc | (1 << row))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut unblinded_commit = C::zero();
+        for column in (0..comb_tables.num_columns).rev() {
+            unblinded_commit = unblinded_commit.double();
+            for (element_teeth, table) in teeth.iter().zip(comb_tables.tables.iter()) {
+                let teeth = element_teeth[column];
+                if teeth != 0 {
+                    unblinded_commit += table[teeth - 1];
+                }
+            }
+        }
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Builds fixed-base window tables with the given window size for `vector_commit_windowed`.
+    /// Each generator gets a table of its first `2^window_bits - 1` multiples, so the tables take
+    /// `generators.len() * (2^window_bits - 1)` points of memory (0.7 MiB for 512 BN254
+    /// generators with `window_bits = 4`).
+    /// In exchange, committing to a vector of full-width scalars costs `num_bits` doublings
+    /// (shared across all generators) and at most `ceil(num_bits / window_bits)` additions per
+    /// element, where `num_bits` is the bit size of the scalar field.
+    /// Pre: 1 <= window_bits <= 16
+    pub fn build_window_table(&self, window_bits: usize) -> WindowTable<C> {
+        assert!((1..=16).contains(&window_bits));
+        let tables = self
+            .generators
+            .iter()
+            .map(|generator| {
+                std::iter::successors(Some(*generator), |multiple| Some(*multiple + *generator))
+                    .take((1 << window_bits) - 1)
+                    .collect()
+            })
+            .collect();
+        WindowTable {
+            window_bits,
+            tables,
         }
     }
 
+    /// Commits to the vector of scalars using the specified blinding factor and the window
+    /// tables built by `build_window_table`: the scalars are split into `window_bits`-bit
+    /// digits, and the digits of all elements at each window are added up from the tables
+    /// between shared doublings.
+    /// Pre: `table` was built by `self.build_window_table`.
+    /// Pre: message.len() <= self.generators.len()
+    /// Post: same result as `scalar_vector_commit(message, blinding)`.
+    /// Note that for a whole row of the Orb's matrix, the MSM of `scalar_vector_commit` is still
+    /// faster; see `benchmark_windowed_commit`.
+    pub fn vector_commit_windowed(
+        &self,
+        message: &[C::Scalar],
+        blinding: &C::Scalar,
+        table: &WindowTable<C>,
+    ) -> C {
+        assert!(message.len() <= self.generators.len());
+        assert_eq!(table.tables.len(), self.generators.len());
+        let window_bits = table.window_bits;
+        let num_windows = (C::Scalar::MODULUS_BIT_SIZE as usize).div_ceil(window_bits);
+        // digits[i][window]: bits `window * window_bits..` of message[i], as a table index
+        let digits = message
+            .iter()
+            .map(|element| {
+                let bits = element.into_bigint().to_bits_le();
+                (0..num_windows)
+                    .map(|window| {
+                        (0..window_bits)
+                            .filter(|bit| bits.get(window * window_bits + bit) == Some(&true))
+                            .fold(0_usize, |acc, bit| acc | (1 << bit))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut unblinded_commit = C::zero();
+        for window in (0..num_windows).rev() {
+            unblinded_commit = (0..window_bits).fold(unblinded_commit, |acc, _| acc.double());
+            for (element_digits, multiples) in digits.iter().zip(table.tables.iter()) {
+                let digit = element_digits[window];
+                if digit != 0 {
+                    unblinded_commit += multiples[digit - 1];
+                }
+            }
+        }
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    // Decomposes a message element in the order of the doublings tables, so that bit `i` of
+    // the result selects `generator_doublings[_][i]`.
+    fn decompose(&self, value: u8) -> Vec<bool> {
+        binary_decomposition(value, self.doublings_bit_order)
+    }
+
+    // Whether the weight-1 bit of the decomposition indexes the weight-1 entry (the generator
+    // itself) of every generator's doublings table, i.e. whether decomposition and tables agree
+    // on bit order. This holds by construction, since the tables and the decomposition take
+    // their order from the same `doublings_bit_order`, so it is only checked in the tests.
+    #[cfg(test)]
+    fn decomposition_matches_doublings(&self) -> bool {
+        let unit_index = match self.decompose(1).iter().position(|bit| *bit) {
+            Some(unit_index) => unit_index,
+            None => return false,
+        };
+        self.generators
+            .iter()
+            .zip(self.generator_doublings.iter())
+            .all(|(generator, doublings)| doublings[unit_index] == *generator)
+    }
+
     /// Sample generators using the public string and the Shake256 hash function.
     /// Pre: public_string.len() >= 32
     /// Post: result.len() == num_generators
     fn sample_generators(num_generators: usize, public_string: &str) -> Vec<C> {
+        Self::sample_generators_with_domain(num_generators, public_string, &[])
+    }
+
+    /// Same as `sample_generators`, additionally absorbing `domain` into the hash after the
+    /// public string, so that each domain yields an independent set of generators.
+    fn sample_generators_with_domain(
+        num_generators: usize,
+        public_string: &str,
+        domain: &[u8],
+    ) -> Vec<C> {
         assert!(public_string.len() >= 32);
         let mut public_string_array: [u8; 32] = [0; 32];
         public_string_array.copy_from_slice(&public_string.as_bytes()[..32]);
         let mut shake = Shake256::default();
         shake.input(public_string_array);
+        shake.input(domain);
 
         let reader = shake.xof_result();
         let mut reader_wrapper = Sha3XofReaderWrapper::new(reader);
@@ -61,29 +568,695 @@ impl<C: PrimeOrderCurve> PedersenCommitter<C> {
         generators
     }
 
+    /// Heuristically checks that the generators (including the blinding generator) satisfy no
+    /// obvious linear relation: that none is the identity, that no two are equal or negations of
+    /// each other, and that none of `trials` random combinations of a few distinct generators with
+    /// small nonzero coefficients is the identity.
+    /// This can not prove independence (which rests on the generators being sampled by hashing);
+    /// it only catches gross setup errors such as duplicated or identity generators.
+    /// The combinations are drawn from `rng`, so that a check can be replayed from a seeded RNG.
+    /// A committer with no message generators has no combinations, so only its blinding
+    /// generator is checked.
+    pub fn heuristic_independence_check(&self, trials: usize, rng: &mut impl Rng) -> bool {
+        let all_generators = std::iter::once(&self.blinding_generator)
+            .chain(self.generators.iter())
+            .collect::<Vec<_>>();
+
+        let mut seen = HashSet::new();
+        for generator in all_generators.iter() {
+            if **generator == C::zero()
+                || !seen.insert(generator.to_bytes_compressed())
+                || seen.contains(&(-**generator).to_bytes_compressed())
+            {
+                return false;
+            }
+        }
+
+        // --- combinations need at least two distinct generators ---
+        if all_generators.len() < 2 {
+            return true;
+        }
+        let max_terms = all_generators.len().min(4);
+        (0..trials).all(|_| {
+            let terms = rng.gen_range(2..=max_terms);
+            let combination = rand::seq::index::sample(rng, all_generators.len(), terms)
+                .into_iter()
+                .fold(C::zero(), |acc, index| {
+                    // a nonzero coefficient in -3..=3
+                    let magnitude = C::Scalar::from(rng.gen_range(1..=3u64));
+                    let coefficient = if rng.gen() { magnitude } else { -magnitude };
+                    acc + *all_generators[index] * coefficient
+                });
+            combination != C::zero()
+        })
+    }
+
     /// Commits to the vector of u8s using the specified blinding factor.
-    /// Uses the precomputed generator powers and the binary decomposition of the u8s to compute the commitment.
-    /// Pre: message.len() <= self.message_generators.len()
-    /// Post: same result as vector_commit, assuming uints are smaller than scalar field order.
+    /// Messages of at least `MSM_THRESHOLD` elements are committed to with a multi-scalar
+    /// multiplication (`vector_commit_with_msm`), shorter ones by summing the precomputed
+    /// generator doublings (`vector_commit_with_doublings`); both give the same commitment.
+    /// Pre: message.len() <= self.generators.len(); see `try_vector_commit` for a
+    /// non-panicking version.
     pub fn vector_commit(&self, message: &[u8], blinding: &C::Scalar) -> C {
+        self.try_vector_commit(message, blinding)
+            .expect("message is longer than the number of generators")
+    }
+
+    /// Same as `vector_commit`, but errors with `HyraxError::MessageTooLong` instead of
+    /// panicking if the message has more elements than there are generators.
+    pub fn try_vector_commit(&self, message: &[u8], blinding: &C::Scalar) -> Result<C, HyraxError> {
+        if message.len() > self.generators.len() {
+            return Err(HyraxError::MessageTooLong {
+                capacity: self.generators.len(),
+                got: message.len(),
+            });
+        }
+        if message.len() >= MSM_THRESHOLD {
+            Ok(self.vector_commit_with_msm(message, blinding))
+        } else {
+            Ok(self.vector_commit_with_doublings(message, blinding))
+        }
+    }
+
+    /// Commits to the vector of u8s using the specified blinding factor, with a bucketed
+    /// (Pippenger) multi-scalar multiplication over the generators. Since the elements are only
+    /// `U8_BITWIDTH` bits wide, a single window covers them: each generator is added into the
+    /// bucket of its element, and the buckets are weighted by running sums. This is the single
+    /// window of `pippenger_msm` (the default `C::msm`), without converting the elements to
+    /// scalars, so it agrees with `C::msm` over the elements as scalars.
+    /// Pre: message.len() <= self.generators.len()
+    pub fn vector_commit_with_msm(&self, message: &[u8], blinding: &C::Scalar) -> C {
+        assert!(message.len() <= self.generators.len());
+        let mut buckets = vec![C::zero(); (1 << U8_BITWIDTH) - 1];
+        message
+            .iter()
+            .zip(self.generators.iter())
+            .filter(|(input, _)| **input != 0)
+            .for_each(|(input, generator)| buckets[*input as usize - 1] += *generator);
+
+        // sum_j j * buckets[j - 1], via running sums from the top bucket down
+        let mut running_sum = C::zero();
+        let mut unblinded_commit = C::zero();
+        buckets.into_iter().rev().for_each(|bucket| {
+            running_sum += bucket;
+            unblinded_commit += running_sum;
+        });
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Commits to the vector of u8s using the specified blinding factor, by summing precomputed
+    /// doublings: `message[i] * generators[i]` is the sum of the doublings `2^j * generators[i]`
+    /// over the set bits `j` of `message[i]`, so each element costs at most `U8_BITWIDTH`
+    /// additions and no doubling. The element commitments are summed and the blinding term
+    /// `blinding * blinding_generator` is added.
+    /// Gives the same commitment as `vector_commit_with_msm`; `vector_commit` uses this for
+    /// messages shorter than `MSM_THRESHOLD`.
+    /// Pre: message.len() <= self.generators.len()
+    pub fn vector_commit_with_doublings(&self, message: &[u8], blinding: &C::Scalar) -> C {
+        assert!(message.len() <= self.generators.len());
+        let unblinded_commit = message
+            .iter()
+            .zip(self.generator_doublings.iter())
+            .map(|(input, generator_doublings)| self.element_commit(generator_doublings, *input))
+            .fold(C::zero(), |acc, value| acc + value);
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Commits to the vector of signed integers using the specified blinding factor.
+    /// Each element contributes the doublings of its magnitude, negated for negative elements,
+    /// so that the result is the commitment to the elements as (signed) scalars.
+    /// Pre: message.len() <= self.generators.len()
+    /// Pre: message[i].unsigned_abs() <= u8::MAX for all i
+    pub fn signed_vector_commit(&self, message: &[i16], blinding: &C::Scalar) -> C {
         assert!(message.len() <= self.generators.len());
         let unblinded_commit = message
             .iter()
             .zip(self.generator_doublings.iter())
             .map(|(input, generator_doublings)| {
-                let bits = binary_decomposition_le(*input);
-                let mut acc = C::zero();
-                bits.into_iter().enumerate().for_each(|(i, bit)| {
-                    if bit {
+                let magnitude = u8::try_from(input.unsigned_abs())
+                    .expect("signed message elements must have magnitude at most 255");
+                let magnitude_commit = self.element_commit(generator_doublings, magnitude);
+                if *input < 0 {
+                    -magnitude_commit
+                } else {
+                    magnitude_commit
+                }
+            })
+            .fold(C::zero(), |acc, value| acc + value);
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Commits to the vector of i8s using the specified blinding factor, as the elements
+    /// (signed) scalar field interpretations, i.e. -5 is committed to as `-Fr::from(5)` rather
+    /// than being bias-shifted.
+    /// Pre: message.len() <= self.generators.len()
+    pub fn i8_vector_commit(&self, message: &[i8], blinding: &C::Scalar) -> C {
+        let (magnitudes, is_negative): (Vec<u8>, Vec<bool>) = message
+            .iter()
+            .map(|value| (value.unsigned_abs(), *value < 0))
+            .unzip();
+        self.integer_vector_commit(&magnitudes, &is_negative, blinding)
+    }
+
+    /// Commits to the vector of u16s using the specified blinding factor. Elements above
+    /// `u8::MAX` are committed to from depth-16 doublings tables if the committer was built with
+    /// `new_with_bitwidth(.., 16)`, and by full scalar multiplication otherwise (see
+    /// `integer_vector_commit`); both give the same commitment.
+    /// Pre: message.len() <= self.generators.len()
+    pub fn u16_vector_commit(&self, message: &[u16], blinding: &C::Scalar) -> C {
+        self.integer_vector_commit(message, &vec![false; message.len()], blinding)
+    }
+
+    /// Commits to the vector of u32s using the specified blinding factor. Elements above
+    /// `u8::MAX` are committed to from depth-32 doublings tables if the committer was built with
+    /// `new_with_bitwidth(.., 32)`, and by full scalar multiplication otherwise (see
+    /// `integer_vector_commit`); both give the same commitment.
+    /// Pre: message.len() <= self.generators.len()
+    pub fn u32_vector_commit(&self, message: &[u32], blinding: &C::Scalar) -> C {
+        self.integer_vector_commit(message, &vec![false; message.len()], blinding)
+    }
+
+    /// Commits to the vector of integers given by their magnitudes `message` and signs
+    /// `is_negative`, using the specified blinding factor: element i contributes
+    /// `message[i] * generators[i]`, negated if `is_negative[i]`. Magnitudes that fit in the
+    /// precomputed doublings (`U8_BITWIDTH` bits, or more for a committer built with
+    /// `new_with_bitwidth`) are committed to from them, wider ones by scalar multiplication.
+    /// Pre: message.len() == is_negative.len() <= self.generators.len()
+    pub fn integer_vector_commit<T: PrimInt>(
+        &self,
+        message: &[T],
+        is_negative: &[bool],
+        blinding: &C::Scalar,
+    ) -> C {
+        assert!(message.len() <= self.generators.len());
+        assert_eq!(message.len(), is_negative.len());
+        let unblinded_commit = message
+            .iter()
+            .zip(is_negative.iter())
+            .enumerate()
+            .map(|(idx, (magnitude, negative))| {
+                let magnitude = magnitude
+                    .to_u64()
+                    .expect("integer message magnitudes must be non-negative");
+                let doublings = &self.generator_doublings[idx];
+                let magnitude_bits = (u64::BITS - magnitude.leading_zeros()) as usize;
+                let magnitude_commit = match u8::try_from(magnitude) {
+                    Ok(small) => self.element_commit(doublings, small),
+                    // only little-endian tables are ever wider than `U8_BITWIDTH`
+                    Err(_) if magnitude_bits <= doublings.len() => (0..magnitude_bits)
+                        .filter(|bit| (magnitude >> bit) & 1 == 1)
+                        .fold(C::zero(), |acc, bit| acc + doublings[bit]),
+                    Err(_) => self.generators[idx] * C::Scalar::from(magnitude),
+                };
+                if *negative {
+                    -magnitude_commit
+                } else {
+                    magnitude_commit
+                }
+            })
+            .fold(C::zero(), |acc, value| acc + value);
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    // Sums the doublings selected by the binary decomposition of `value`, i.e. computes
+    // `value * generator` from the generator's doublings table.
+    fn element_commit(&self, generator_doublings: &[C], value: u8) -> C {
+        let bits = self.decompose(value);
+        let mut acc = C::zero();
+        bits.into_iter().enumerate().for_each(|(i, bit)| {
+            if bit {
+                acc += generator_doublings[i];
+            }
+        });
+        acc
+    }
+
+    /// Commits to the vector of u8s as `vector_commit_with_doublings` does, additionally recording
+    /// the operations performed as a `CommitTrace`. The doublings are summed for every message
+    /// length, so for messages of at least `MSM_THRESHOLD` elements the trace does not follow
+    /// `vector_commit` (which takes the MSM path), though the commitment is the same.
+    /// Pre: message.len() <= self.generators.len()
+    #[cfg(feature = "trace")]
+    pub fn commit_with_trace(&self, message: &[u8], blinding: &C::Scalar) -> (C, CommitTrace<C>) {
+        assert!(message.len() <= self.generators.len());
+        let mut operations = Vec::with_capacity(message.len() + 1);
+        let mut acc = C::zero();
+        message
+            .iter()
+            .zip(self.generator_doublings.iter())
+            .enumerate()
+            .for_each(|(generator_index, (input, generator_doublings))| {
+                let bits = self.decompose(*input);
+                bits.iter().enumerate().for_each(|(i, bit)| {
+                    if *bit {
                         acc += generator_doublings[i];
                     }
                 });
-                acc
-            })
-            .fold(C::zero(), |acc, value| acc + value);
+                operations.push(TraceOperation::MessageElement {
+                    generator_index,
+                    bits,
+                });
+            });
+        operations.push(TraceOperation::Blinding {
+            blinding: *blinding,
+        });
+
+        (
+            acc + self.blinding_generator * *blinding,
+            CommitTrace { operations },
+        )
+    }
+
+    /// Commits to the vector of u8s as `vector_commit` does, additionally returning the
+    /// prefix of `self.generators` that the message elements were committed against.
+    /// Pre: message.len() <= self.generators.len()
+    /// Post: result.1.len() == message.len()
+    pub fn vector_commit_with_used_generators(
+        &self,
+        message: &[u8],
+        blinding: &C::Scalar,
+    ) -> (C, &[C]) {
+        let commit = self.vector_commit(message, blinding);
+        (commit, &self.generators[..message.len()])
+    }
+
+    /// Commits to the run-length-encoded vector of u8s using the specified blinding factor,
+    /// without expanding it: each `(value, run_len)` commits `value` to the next `run_len`
+    /// generators, by adding up their precomputed doublings for the set bits of `value`.
+    /// Pre: the total run length is <= self.generators.len(); see `try_run_length_vector_commit`
+    /// for a non-panicking version.
+    /// Post: same result as vector_commit on the expanded message.
+    pub fn run_length_vector_commit(&self, runs: &[(u8, usize)], blinding: &C::Scalar) -> C {
+        self.try_run_length_vector_commit(runs, blinding)
+            .expect("total run length exceeds the number of generators")
+    }
+
+    /// Same as `run_length_vector_commit`, but errors with `HyraxError::MessageTooLong` instead of
+    /// panicking if the total run length exceeds the number of generators (with `got` saturated
+    /// at `usize::MAX` if the total overflows a `usize`).
+    pub fn try_run_length_vector_commit(
+        &self,
+        runs: &[(u8, usize)],
+        blinding: &C::Scalar,
+    ) -> Result<C, HyraxError> {
+        let too_long = |got| HyraxError::MessageTooLong {
+            capacity: self.generators.len(),
+            got,
+        };
+        let total_len = runs
+            .iter()
+            .try_fold(0_usize, |acc, (_, run_len)| acc.checked_add(*run_len))
+            .ok_or(too_long(usize::MAX))?;
+        if total_len > self.generators.len() {
+            return Err(too_long(total_len));
+        }
+
+        let mut offset = 0;
+        let mut acc = C::zero();
+        runs.iter().for_each(|(value, run_len)| {
+            let bits = self.decompose(*value);
+            self.generator_doublings[offset..offset + run_len]
+                .iter()
+                .for_each(|generator_doublings| {
+                    bits.iter().enumerate().for_each(|(i, bit)| {
+                        if *bit {
+                            acc += generator_doublings[i];
+                        }
+                    });
+                });
+            offset += run_len;
+        });
+
+        Ok(acc + self.blinding_generator * *blinding)
+    }
+
+    /// Commits to the vector of bits using the specified blinding factor.
+    /// Each set bit contributes its generator directly, so no doublings are needed.
+    /// Pre: message.len() <= self.generators.len()
+    /// Post: same result as vector_commit on the message cast to 0/1 u8s.
+    pub fn bit_vector_commit(&self, message: &[bool], blinding: &C::Scalar) -> C {
+        assert!(message.len() <= self.generators.len());
+        let unblinded_commit = message
+            .iter()
+            .zip(self.generators.iter())
+            .filter(|(bit, _)| **bit)
+            .fold(C::zero(), |acc, (_, generator)| acc + *generator);
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Commits to the vector of u8s, each scaled by its public weight, i.e. computes
+    /// `sum_i weights[i] * message[i] * generators[i] + blinding * blinding_generator`.
+    /// This is a commitment to `message` under the generators `weights[i] * generators[i]`.
+    /// Pre: message.len() <= self.generators.len()
+    /// Pre: weights.len() == message.len()
+    pub fn weighted_vector_commit(
+        &self,
+        message: &[u8],
+        weights: &[C::Scalar],
+        blinding: &C::Scalar,
+    ) -> C {
+        assert!(message.len() <= self.generators.len());
+        assert_eq!(weights.len(), message.len());
+        let scalars = message
+            .iter()
+            .zip(weights.iter())
+            .map(|(input, weight)| *weight * C::Scalar::from(*input as u64))
+            .collect::<Vec<_>>();
+        let unblinded_commit = C::msm(&scalars, &self.generators[..message.len()]);
 
         unblinded_commit + self.blinding_generator * *blinding
     }
+
+    /// Commits to the vector of scalars using the specified blinding factor, i.e. computes
+    /// `sum_i message[i] * generators[i] + blinding * blinding_generator` with a full-width
+    /// multi-scalar multiplication (the doublings tables only cover `U8_BITWIDTH` bits).
+    /// Pre: message.len() <= self.generators.len()
+    pub fn scalar_vector_commit(&self, message: &[C::Scalar], blinding: &C::Scalar) -> C {
+        assert!(message.len() <= self.generators.len());
+        let unblinded_commit = C::msm(message, &self.generators[..message.len()]);
+
+        unblinded_commit + self.blinding_generator * *blinding
+    }
+
+    /// Commits to the single scalar using the specified blinding factor and the first generator,
+    /// i.e. computes `scalar * generators[0] + blinding * blinding_generator`.
+    /// Pre: !self.generators.is_empty()
+    pub fn scalar_commit(&self, scalar: &C::Scalar, blinding: &C::Scalar) -> C {
+        self.generators[0] * *scalar + self.blinding_generator * *blinding
+    }
+}
+
+/// Derives the committer for the given rotation epoch: same number of generators as `committer`,
+/// re-derived from `public_string || epoch` (epoch as little-endian bytes), so each epoch has an
+/// independent set of generators, none of which coincides with those of `PedersenCommitter::new`.
+/// Since the committer does not keep its public string, it has to be passed in again.
+/// Pre: public_string.len() >= 32
+pub fn rotate_generators<C: PrimeOrderCurve>(
+    committer: &PedersenCommitter<C>,
+    public_string: &str,
+    epoch: u64,
+) -> PedersenCommitter<C> {
+    let all_generators = PedersenCommitter::<C>::sample_generators_with_domain(
+        committer.generators.len() + 1,
+        public_string,
+        &epoch.to_le_bytes(),
+    );
+    let blinding_generator_h = all_generators[0];
+    let generators_g_i = all_generators[1..].to_vec();
+
+    PedersenCommitter::from_parts(generators_g_i, blinding_generator_h)
+}
+
+/// Combines committers additively: the i-th generator (and the blinding generator) of the result
+/// is the sum of the corresponding generators of `committers`. By the additive homomorphism, a
+/// commitment under the combined committer equals the sum of the per-committer commitments to the
+/// same message with the same blinding factor, so each party's setup contributes to the result.
+/// Pre: !committers.is_empty(), and all committers have the same number of generators.
+pub fn combine_committers<C: PrimeOrderCurve>(
+    committers: &[PedersenCommitter<C>],
+) -> PedersenCommitter<C> {
+    assert!(!committers.is_empty());
+    let num_generators = committers[0].generators.len();
+    assert!(committers
+        .iter()
+        .all(|committer| committer.generators.len() == num_generators));
+
+    let generators = (0..num_generators)
+        .map(|i| {
+            committers
+                .iter()
+                .fold(C::zero(), |acc, committer| acc + committer.generators[i])
+        })
+        .collect();
+    let blinding_generator = committers.iter().fold(C::zero(), |acc, committer| {
+        acc + committer.blinding_generator
+    });
+
+    PedersenCommitter::from_parts(generators, blinding_generator)
+}
+
+/// A commitment scheme over `n` parties' committers in which the opening is shared k-of-n: the
+/// committer is `combine_committers` of the parties' committers, and the blinding factor of each
+/// commitment is Shamir-shared among the parties, so that any `threshold` of them can together
+/// reconstruct it (and thereby open the commitment), while fewer learn nothing about it.
+pub struct ThresholdCommitter<C: PrimeOrderCurve> {
+    /// the combined committer, see `combine_committers`
+    pub committer: PedersenCommitter<C>,
+    threshold: usize,
+    num_parties: usize,
+}
+
+/// A party's share of a commitment's blinding factor: the evaluation at `index` (in
+/// `1..=num_parties`, the party's position in the committers passed to
+/// `ThresholdCommitter::new`) of the sharing polynomial, whose value at 0 is the blinding factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlindingShare<C: PrimeOrderCurve> {
+    pub index: u64,
+    pub value: C::Scalar,
+}
+
+impl<C: PrimeOrderCurve> ThresholdCommitter<C> {
+    /// Combines the parties' committers, any `threshold` of the `committers.len()` parties being
+    /// able to open a commitment.
+    /// Errors with `HyraxError::InvalidThreshold` unless `1 <= threshold <= committers.len()`.
+    /// Pre: all committers have the same number of generators.
+    pub fn new(committers: &[PedersenCommitter<C>], threshold: usize) -> Result<Self, HyraxError> {
+        if threshold == 0 || threshold > committers.len() {
+            return Err(HyraxError::InvalidThreshold {
+                threshold,
+                num_parties: committers.len(),
+            });
+        }
+        Ok(Self {
+            committer: combine_committers(committers),
+            threshold,
+            num_parties: committers.len(),
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn num_parties(&self) -> usize {
+        self.num_parties
+    }
+
+    /// Commits to the vector of u8s under the combined committer with a blinding factor drawn
+    /// from `rng`, returning the commitment and the parties' shares of the blinding factor (share
+    /// `i` for the party at index `i + 1`). The blinding factor itself is not returned.
+    /// Errors with `HyraxError::MessageTooLong` if the message is longer than the generators.
+    pub fn commit(
+        &self,
+        message: &[u8],
+        rng: &mut impl Rng,
+    ) -> Result<(C, Vec<BlindingShare<C>>), HyraxError> {
+        // --- A random polynomial of degree threshold - 1, its constant term the blinding factor ---
+        let coefficients = (0..self.threshold)
+            .map(|_| C::Scalar::rand(rng))
+            .collect::<Vec<_>>();
+        let commitment = self
+            .committer
+            .try_vector_commit(message, &coefficients[0])?;
+        let shares = (1..=self.num_parties as u64)
+            .map(|index| BlindingShare {
+                index,
+                value: coefficients
+                    .iter()
+                    .rev()
+                    .fold(C::Scalar::from(0u64), |acc, coefficient| {
+                        acc * C::Scalar::from(index) + coefficient
+                    }),
+            })
+            .collect();
+        Ok((commitment, shares))
+    }
+
+    /// Reconstructs the blinding factor from the first `threshold` of `shares`, by Lagrange
+    /// interpolation at 0.
+    /// Errors with `HyraxError::InvalidShareIndex` for a share whose index is not in
+    /// `1..=num_parties` or repeats that of an earlier share, and with
+    /// `HyraxError::InsufficientShares` if there are fewer than `threshold` shares.
+    pub fn reconstruct_blinding(
+        &self,
+        shares: &[BlindingShare<C>],
+    ) -> Result<C::Scalar, HyraxError> {
+        let mut seen = HashSet::new();
+        for share in shares {
+            if share.index == 0
+                || share.index > self.num_parties as u64
+                || !seen.insert(share.index)
+            {
+                return Err(HyraxError::InvalidShareIndex(share.index));
+            }
+        }
+        if shares.len() < self.threshold {
+            return Err(HyraxError::InsufficientShares {
+                threshold: self.threshold,
+                got: shares.len(),
+            });
+        }
+
+        let shares = &shares[..self.threshold];
+        Ok(shares.iter().fold(C::Scalar::from(0u64), |acc, share| {
+            let x_i = C::Scalar::from(share.index);
+            // --- the Lagrange basis polynomial of x_i, evaluated at 0 ---
+            let (numerator, denominator) = shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(
+                    (C::Scalar::from(1u64), C::Scalar::from(1u64)),
+                    |(num, den), other| {
+                        let x_j = C::Scalar::from(other.index);
+                        (num * x_j, den * (x_j - x_i))
+                    },
+                );
+            let basis = numerator
+                * denominator
+                    .inverse()
+                    .expect("the share indices are distinct, so no factor is zero");
+            acc + share.value * basis
+        }))
+    }
+
+    /// Checks that `commitment` opens to `message` under the blinding factor reconstructed from
+    /// `shares`. Errors as `reconstruct_blinding`, and as `commit` for too long a message.
+    pub fn verify_opening(
+        &self,
+        commitment: &C,
+        message: &[u8],
+        shares: &[BlindingShare<C>],
+    ) -> Result<bool, HyraxError> {
+        let blinding = self.reconstruct_blinding(shares)?;
+        Ok(self.committer.try_vector_commit(message, &blinding)? == *commitment)
+    }
+}
+
+/// Adds the public vector `public` to the vector committed to by `commitment`, i.e. returns
+/// `commitment + vector_committer.vector_commit(public, 0)`, a commitment to `message + public`
+/// (elementwise, in the scalar field) with the original blinding factor. This avoids
+/// recommitting to the whole message when only a public addend (e.g. a public mask) changes.
+/// Errors with `HyraxError::MessageTooLong` if `public` is longer than the generators.
+pub fn add_public_vector<C: PrimeOrderCurve>(
+    commitment: C,
+    public: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<C, HyraxError> {
+    Ok(commitment + vector_committer.try_vector_commit(public, &C::Scalar::from(0u64))?)
+}
+
+/// Parses generators stored in the EVM precompile point format: each point is its affine `(x, y)`,
+/// each coordinate big endian and (for BN254) 32 bytes wide, concatenated without any flags.
+/// The first point is taken to be the blinding generator and the rest the message generators,
+/// in the same order as `PedersenCommitter::sample_generators` produces them for `new`.
+/// Errors with `HyraxError::InvalidByteLength` if the bytes are empty or not a whole number of
+/// points, with `HyraxError::PointNotOnCurve` for a point not on the curve (including the EVM
+/// encoding `(0, 0)` of the identity, which is not a valid generator), and with
+/// `HyraxError::NonCanonicalCoordinate` for a coordinate that is not reduced modulo the field order.
+pub fn generators_from_evm_registry_bytes<C: PrimeOrderCurve>(
+    bytes: &[u8],
+) -> Result<(Vec<C>, C), HyraxError> {
+    let coordinate_width = (C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH - 1) / 2;
+    let point_width = 2 * coordinate_width;
+    if bytes.is_empty() || bytes.len() % point_width != 0 {
+        return Err(HyraxError::InvalidByteLength {
+            expected: bytes.len().div_ceil(point_width).max(1) * point_width,
+            got: bytes.len(),
+        });
+    }
+    let mut points = bytes
+        .chunks(point_width)
+        .map(|evm_bytes| {
+            // --- Into the crate's uncompressed layout: a zero flag byte, then LE coordinates ---
+            let (x_be, y_be) = evm_bytes.split_at(coordinate_width);
+            let uncompressed = std::iter::once(0_u8)
+                .chain(x_be.iter().rev().copied())
+                .chain(y_be.iter().rev().copied())
+                .collect::<Vec<u8>>();
+            C::try_from_bytes_uncompressed(&uncompressed)
+        })
+        .collect::<Result<Vec<C>, HyraxError>>()?;
+    let blinding_generator = points.remove(0);
+    Ok((points, blinding_generator))
+}
+
+/// Serializes the committer's generators in the EVM precompile point format read by
+/// `generators_from_evm_registry_bytes`: the blinding generator first, then the message
+/// generators.
+/// Pre: none of the generators is the identity, which the format cannot represent.
+pub fn generators_to_evm_registry_bytes<C: PrimeOrderCurve>(
+    committer: &PedersenCommitter<C>,
+) -> Vec<u8> {
+    let coordinate_width = (C::UNCOMPRESSED_CURVE_POINT_BYTEWIDTH - 1) / 2;
+    std::iter::once(&committer.blinding_generator)
+        .chain(committer.generators.iter())
+        .flat_map(|generator| {
+            assert!(*generator != C::zero());
+            let uncompressed = generator.to_bytes_uncompressed();
+            let (x_le, y_le) = uncompressed[1..].split_at(coordinate_width);
+            x_le.iter()
+                .rev()
+                .chain(y_le.iter().rev())
+                .copied()
+                .collect::<Vec<u8>>()
+        })
+        .collect()
+}
+
+/// Builds a committer from generators pinned in the EVM registry format (see
+/// `generators_from_evm_registry_bytes`).
+pub fn committer_from_evm_registry_bytes<C: PrimeOrderCurve>(
+    bytes: &[u8],
+) -> Result<PedersenCommitter<C>, HyraxError> {
+    let (generators, blinding_generator) = generators_from_evm_registry_bytes(bytes)?;
+    Ok(PedersenCommitter::from_parts(
+        generators,
+        blinding_generator,
+    ))
+}
+
+// Errors with `HyraxError::BitwidthExceedsField` unless integers of `int_abs_val_bitwidth` bits
+// are all below the scalar field's modulus, i.e. unless
+// `int_abs_val_bitwidth < C::Scalar::MODULUS_BIT_SIZE`.
+fn validate_bitwidth<C: PrimeOrderCurve>(int_abs_val_bitwidth: usize) -> Result<(), HyraxError> {
+    let field_bits = C::Scalar::MODULUS_BIT_SIZE as usize;
+    if int_abs_val_bitwidth >= field_bits {
+        return Err(HyraxError::BitwidthExceedsField {
+            bitwidth: int_abs_val_bitwidth,
+            field_bits,
+        });
+    }
+    Ok(())
+}
+
+// Concatenates the `to_bytes_compressed` encodings of the points.
+fn points_to_bytes_compressed<'a, C: PrimeOrderCurve + 'a>(
+    points: impl IntoIterator<Item = &'a C>,
+) -> Vec<u8> {
+    points
+        .into_iter()
+        .flat_map(|point| point.to_bytes_compressed())
+        .collect()
+}
+
+// Parses the concatenation of `to_bytes_compressed` encodings of a nonempty list of points.
+fn points_from_bytes_compressed<C: PrimeOrderCurve>(bytes: &[u8]) -> Result<Vec<C>, HyraxError> {
+    let point_width = C::COMPRESSED_CURVE_POINT_BYTEWIDTH;
+    if bytes.is_empty() || bytes.len() % point_width != 0 {
+        return Err(HyraxError::InvalidByteLength {
+            expected: bytes.len().div_ceil(point_width).max(1) * point_width,
+            got: bytes.len(),
+        });
+    }
+    bytes
+        .chunks(point_width)
+        .map(C::try_from_bytes_compressed)
+        .collect()
 }
 
 // Compute the little endian binary decomposition of the provided integer value.
@@ -97,6 +1270,23 @@ fn binary_decomposition_le<T: PrimInt>(value: T) -> Vec<bool> {
         .collect()
 }
 
+// Compute the big endian binary decomposition of the provided integer value.
+// Pre: value is non-negative.
+// Post: result is binary_decomposition_le(value), reversed.
+fn binary_decomposition_be<T: PrimInt>(value: T) -> Vec<bool> {
+    let mut bits = binary_decomposition_le(value);
+    bits.reverse();
+    bits
+}
+
+// Compute the binary decomposition of the provided integer value in the given bit order.
+fn binary_decomposition<T: PrimInt>(value: T, bit_order: BitOrder) -> Vec<bool> {
+    match bit_order {
+        BitOrder::LittleEndian => binary_decomposition_le(value),
+        BitOrder::BigEndian => binary_decomposition_be(value),
+    }
+}
+
 // Returns the vector [2^i * base for i in 0..bitwidth]
 // Post: powers.len() == bitwidth
 fn precompute_doublings<G: PrimeOrderCurve>(base: G, bitwidth: usize) -> Vec<G> {
@@ -108,3 +1298,18 @@ fn precompute_doublings<G: PrimeOrderCurve>(base: G, bitwidth: usize) -> Vec<G>
     }
     powers
 }
+
+// Returns the doublings of `precompute_doublings`, laid out in the given bit order, i.e. reversed
+// for `BitOrder::BigEndian`.
+// Post: powers.len() == bitwidth
+fn precompute_doublings_with_bit_order<G: PrimeOrderCurve>(
+    base: G,
+    bitwidth: usize,
+    bit_order: BitOrder,
+) -> Vec<G> {
+    let mut powers = precompute_doublings(base, bitwidth);
+    if bit_order == BitOrder::BigEndian {
+        powers.reverse();
+    }
+    powers
+}
@@ -5,6 +5,7 @@ use super::super::curves::PrimeOrderCurve;
 use super::*;
 use ark_bn254::Fr as Bn256Scalar;
 use ark_bn254::G1Projective as Bn256Point;
+use rand::Rng;
 
 #[test]
 /// tests whether when we run the generator sampling twice we still get the same generators
@@ -21,6 +22,55 @@ fn test_public_sampling() {
     );
 }
 
+#[test]
+fn test_sampled_generators_are_distinct_points_on_the_curve() {
+    use itertools::Itertools;
+
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let all_generators = std::iter::once(committer.blinding_generator)
+        .chain(committer.generators.iter().copied())
+        .collect_vec();
+    assert!(all_generators
+        .iter()
+        .all(|gen| gen.is_on_curve() && *gen != <Bn256Point as PrimeOrderCurve>::zero()));
+    assert!(all_generators.iter().all_unique());
+
+    // --- Distinct public strings give distinct generators ---
+    let other: PedersenCommitter<Bn256Point> = PedersenCommitter::new(
+        1 << LOG_NUM_COLS,
+        "a different public string of at least 32 bytes",
+    );
+    assert!(other
+        .generators
+        .iter()
+        .all(|gen| !all_generators.contains(gen)));
+}
+
+#[test]
+fn test_random_committer_is_reproducible_from_seed() {
+    use crate::entropy::test_seed;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    let seed = test_seed("random committer").bytes();
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::random(8, &mut ChaCha20Rng::from_seed(seed));
+    let committer_2: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::random(8, &mut ChaCha20Rng::from_seed(seed));
+    assert_eq!(committer.generators.len(), 8);
+    assert_eq!(committer.generators, committer_2.generators);
+    assert_eq!(committer.blinding_generator, committer_2.blinding_generator);
+    assert!(!committer.generators.contains(&committer.blinding_generator));
+
+    let message: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    let blinding = Bn256Scalar::from(9u64);
+    assert_eq!(
+        committer.vector_commit(&message, &blinding),
+        committer_2.vector_commit(&message, &blinding)
+    );
+}
+
 #[test]
 fn test_commitment_with_random_generators_isnt_identity() {
     let zero: Bn256Scalar = Bn256Scalar::from(0_u64);
@@ -32,6 +82,32 @@ fn test_commitment_with_random_generators_isnt_identity() {
     assert!(commit != identity);
 }
 
+#[test]
+fn test_random_committer_redraws_the_identity() {
+    // --- A stub sampler that returns the identity first, and again between generators ---
+    let generator = Bn256Point::generator();
+    let mut samples = vec![
+        Bn256Point::zero(),
+        generator,
+        Bn256Point::zero(),
+        Bn256Point::zero(),
+        generator + generator,
+        generator + generator + generator,
+    ]
+    .into_iter();
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::from_non_identity_samples(2, || samples.next().unwrap());
+    assert_eq!(committer.blinding_generator, generator);
+    assert_eq!(
+        committer.generators,
+        vec![generator + generator, generator + generator + generator]
+    );
+    assert!(samples.next().is_none());
+
+    let commit = committer.vector_commit(&[1, 0], &Bn256Scalar::from(0u64));
+    assert!(commit != Bn256Point::zero());
+}
+
 #[test]
 fn test_blinding_factor_dependence() {
     let committer: PedersenCommitter<Bn256Point> =
@@ -52,6 +128,26 @@ fn test_too_long_messages_fail() {
     let _commit = committer.vector_commit(&message, &blinding_factor);
 }
 
+#[test]
+fn test_try_vector_commit() {
+    use crate::error::HyraxError;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    let message: Vec<u8> = vec![5, 7, 0, 255];
+    assert_eq!(
+        committer.try_vector_commit(&message, &blinding_factor),
+        Ok(committer.vector_commit(&message, &blinding_factor))
+    );
+    assert_eq!(
+        committer.try_vector_commit(&[1, 2, 3, 4, 5], &blinding_factor),
+        Err(HyraxError::MessageTooLong {
+            capacity: 4,
+            got: 5
+        })
+    );
+}
+
 #[test]
 fn test_permutation() {
     let committer: PedersenCommitter<Bn256Point> =
@@ -84,3 +180,927 @@ fn test_bit_decomposition_lsb() {
         vec![true, false, true, false, false, false, false, false]
     );
 }
+
+#[test]
+fn test_bit_decomposition_msb() {
+    let uint: u8 = 5;
+    let bits = binary_decomposition_be(uint);
+    assert_eq!(
+        bits,
+        vec![false, false, false, false, false, true, false, true]
+    );
+}
+
+#[test]
+fn test_both_bit_orders_commit_correctly() {
+    let little_endian: PedersenCommitter<Bn256Point> = PedersenCommitter::new(3, PUBLIC_STRING);
+    let big_endian = PedersenCommitter::from_generators_with_bit_order(
+        little_endian.generators.clone(),
+        little_endian.blinding_generator,
+        BitOrder::BigEndian,
+    );
+    assert!(little_endian.decomposition_matches_doublings());
+    assert!(big_endian.decomposition_matches_doublings());
+
+    let message: Vec<u8> = vec![1, 130, 255];
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(9u64);
+    let expected = message.iter().zip(little_endian.generators.iter()).fold(
+        little_endian.blinding_generator * blinding_factor,
+        |acc, (input, generator)| acc + *generator * Bn256Scalar::from(*input as u64),
+    );
+    assert_eq!(
+        little_endian.vector_commit(&message, &blinding_factor),
+        expected
+    );
+    assert_eq!(
+        big_endian.vector_commit(&message, &blinding_factor),
+        expected
+    );
+
+    // --- A table in one order read with the other decomposition does not line up ---
+    let mismatched = PedersenCommitter {
+        doublings_bit_order: BitOrder::LittleEndian,
+        ..PedersenCommitter::from_parts_with_bit_order(
+            little_endian.generators.clone(),
+            little_endian.blinding_generator,
+            BitOrder::BigEndian,
+        )
+    };
+    assert!(!mismatched.decomposition_matches_doublings());
+
+    // --- A single corrupted table past the first generator is caught as well ---
+    let mut corrupted = PedersenCommitter::<Bn256Point>::new(3, PUBLIC_STRING);
+    corrupted.generator_doublings[2].reverse();
+    assert!(!corrupted.decomposition_matches_doublings());
+}
+
+#[test]
+fn test_bit_vector_commit_matches_vector_commit() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "accountable magic something something");
+    let bits = vec![true, false, true, true];
+    let message: Vec<u8> = bits.iter().map(|bit| *bit as u8).collect();
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    assert_eq!(
+        committer.bit_vector_commit(&bits, &blinding_factor),
+        committer.vector_commit(&message, &blinding_factor)
+    );
+}
+
+#[test]
+fn test_used_generators_for_short_message() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "accountable magic something something");
+    let message: Vec<u8> = vec![5, 7];
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    let (commit, used_generators) =
+        committer.vector_commit_with_used_generators(&message, &blinding_factor);
+    assert_eq!(commit, committer.vector_commit(&message, &blinding_factor));
+    assert_eq!(used_generators.len(), message.len());
+    assert_eq!(used_generators, &committer.generators[..2]);
+}
+
+#[test]
+fn test_run_length_vector_commit_matches_expanded() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, "accountable magic something something");
+    let runs = vec![(5_u8, 3), (0, 2), (200, 1), (7, 2)];
+    let expanded: Vec<u8> = vec![5, 5, 5, 0, 0, 200, 7, 7];
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    assert_eq!(
+        committer.run_length_vector_commit(&runs, &blinding_factor),
+        committer.vector_commit(&expanded, &blinding_factor)
+    );
+
+    // --- Runs longer than the generators, in total or by overflowing a usize ---
+    assert_eq!(
+        committer.try_run_length_vector_commit(&[(1, 5), (2, 4)], &blinding_factor),
+        Err(HyraxError::MessageTooLong {
+            capacity: 8,
+            got: 9
+        })
+    );
+    assert_eq!(
+        committer.try_run_length_vector_commit(&[(1, 2), (2, usize::MAX)], &blinding_factor),
+        Err(HyraxError::MessageTooLong {
+            capacity: 8,
+            got: usize::MAX
+        })
+    );
+}
+
+#[test]
+fn test_combined_committer_commits_to_sum() {
+    let committers: Vec<PedersenCommitter<Bn256Point>> = [
+        "accountable magic something something",
+        "another thirty-two byte public string",
+        "and a third party's public string here",
+    ]
+    .iter()
+    .map(|public_string| PedersenCommitter::new(3, public_string))
+    .collect();
+    let combined = combine_committers(&committers);
+
+    let message: Vec<u8> = vec![5, 7, 250];
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    let sum_of_commits = committers
+        .iter()
+        .map(|committer| committer.vector_commit(&message, &blinding_factor))
+        .fold(Bn256Point::default(), |acc, commit| acc + commit);
+    assert_eq!(
+        combined.vector_commit(&message, &blinding_factor),
+        sum_of_commits
+    );
+}
+
+#[test]
+fn test_threshold_committer_any_k_shares_open() {
+    use crate::entropy::test_seed;
+    use crate::error::HyraxError;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    let mut rng =
+        ChaCha20Rng::from_seed(test_seed("test_threshold_committer_any_k_shares_open").bytes());
+    let committers: Vec<PedersenCommitter<Bn256Point>> = [
+        "accountable magic something something",
+        "another thirty-two byte public string",
+        "and a third party's public string here",
+    ]
+    .iter()
+    .map(|public_string| PedersenCommitter::new(3, public_string))
+    .collect();
+    let threshold_committer = ThresholdCommitter::new(&committers, 2).unwrap();
+
+    let message: Vec<u8> = vec![5, 7, 250];
+    let (commitment, shares) = threshold_committer.commit(&message, &mut rng).unwrap();
+    assert_eq!(shares.len(), 3);
+
+    // --- Every pair of shares reconstructs the same blinding factor and opens the commitment,
+    // which is the sum of the per-committer commitments under it ---
+    let blinding = threshold_committer
+        .reconstruct_blinding(&[shares[0], shares[1]])
+        .unwrap();
+    let sum_of_commits = committers
+        .iter()
+        .map(|committer| committer.vector_commit(&message, &blinding))
+        .fold(Bn256Point::default(), |acc, commit| acc + commit);
+    assert_eq!(commitment, sum_of_commits);
+    for pair in [[shares[0], shares[2]], [shares[2], shares[1]]] {
+        assert_eq!(
+            threshold_committer.reconstruct_blinding(&pair),
+            Ok(blinding)
+        );
+        assert_eq!(
+            threshold_committer.verify_opening(&commitment, &message, &pair),
+            Ok(true)
+        );
+    }
+    assert_eq!(
+        threshold_committer.verify_opening(&commitment, &[5, 7, 251], &shares),
+        Ok(false)
+    );
+
+    // --- A tampered share yields a different blinding factor ---
+    let tampered = BlindingShare {
+        value: shares[1].value + Bn256Scalar::from(1u64),
+        ..shares[1]
+    };
+    assert_eq!(
+        threshold_committer.verify_opening(&commitment, &message, &[shares[0], tampered]),
+        Ok(false)
+    );
+
+    // --- Too few, out of range and repeated shares ---
+    assert_eq!(
+        threshold_committer.reconstruct_blinding(&shares[..1]),
+        Err(HyraxError::InsufficientShares {
+            threshold: 2,
+            got: 1
+        })
+    );
+    let out_of_range = BlindingShare {
+        index: 4,
+        ..shares[0]
+    };
+    assert_eq!(
+        threshold_committer.reconstruct_blinding(&[shares[0], out_of_range]),
+        Err(HyraxError::InvalidShareIndex(4))
+    );
+    assert_eq!(
+        threshold_committer.reconstruct_blinding(&[shares[0], shares[0]]),
+        Err(HyraxError::InvalidShareIndex(1))
+    );
+
+    for threshold in [0, 4] {
+        assert!(matches!(
+            ThresholdCommitter::new(&committers, threshold),
+            Err(HyraxError::InvalidThreshold { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn test_trace_replays_to_commitment() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "accountable magic something something");
+    let message: Vec<u8> = vec![5, 0, 255, 128];
+    let blinding_factor: Bn256Scalar = Bn256Scalar::from(4u64);
+    let (commit, trace) = committer.commit_with_trace(&message, &blinding_factor);
+    assert_eq!(commit, committer.vector_commit(&message, &blinding_factor));
+    assert_eq!(trace.operations.len(), message.len() + 1);
+
+    // --- Replay naively, with full scalar multiplications instead of the doublings ---
+    let replayed = trace
+        .operations
+        .iter()
+        .fold(Bn256Point::default(), |acc, operation| match operation {
+            TraceOperation::MessageElement {
+                generator_index,
+                bits,
+            } => {
+                let scalar = bits
+                    .iter()
+                    .rev()
+                    .fold(Bn256Scalar::from(0u64), |scalar, bit| {
+                        scalar + scalar + Bn256Scalar::from(*bit as u64)
+                    });
+                acc + committer.generators[*generator_index] * scalar
+            }
+            TraceOperation::Blinding { blinding } => acc + committer.blinding_generator * *blinding,
+        });
+    assert_eq!(replayed, commit);
+}
+
+#[test]
+fn test_weighted_vector_commit() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let message: Vec<u8> = vec![9, 0, 200, 77];
+    let blinding_factor = Bn256Scalar::from(11u64);
+
+    // --- Unit weights give back the plain commitment ---
+    let ones = vec![Bn256Scalar::from(1u64); message.len()];
+    assert_eq!(
+        committer.weighted_vector_commit(&message, &ones, &blinding_factor),
+        committer.vector_commit(&message, &blinding_factor)
+    );
+
+    // --- Other weights match committing under the scaled generators ---
+    let weights: Vec<Bn256Scalar> = (2..6u64).map(Bn256Scalar::from).collect();
+    let expected = message
+        .iter()
+        .zip(weights.iter().zip(committer.generators.iter()))
+        .fold(
+            committer.blinding_generator * blinding_factor,
+            |acc, (input, (weight, generator))| {
+                acc + *generator * (*weight * Bn256Scalar::from(*input as u64))
+            },
+        );
+    assert_eq!(
+        committer.weighted_vector_commit(&message, &weights, &blinding_factor),
+        expected
+    );
+}
+
+#[test]
+fn test_rotated_generators_are_deterministic_per_epoch() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let epoch_0 = rotate_generators(&committer, PUBLIC_STRING, 0);
+    let epoch_1 = rotate_generators(&committer, PUBLIC_STRING, 1);
+
+    assert_eq!(epoch_0.generators.len(), committer.generators.len());
+    assert_eq!(
+        epoch_0.generators,
+        rotate_generators(&committer, PUBLIC_STRING, 0).generators
+    );
+    assert_eq!(
+        epoch_1.blinding_generator,
+        rotate_generators(&committer, PUBLIC_STRING, 1).blinding_generator
+    );
+
+    assert_ne!(epoch_0.generators, epoch_1.generators);
+    assert_ne!(epoch_0.blinding_generator, epoch_1.blinding_generator);
+    assert_ne!(epoch_0.generators, committer.generators);
+}
+
+#[test]
+fn test_signed_vector_commit() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let message: Vec<i16> = vec![-255, 0, 17, -1];
+    let blinding_factor = Bn256Scalar::from(2u64);
+    let expected = message.iter().zip(committer.generators.iter()).fold(
+        committer.blinding_generator * blinding_factor,
+        |acc, (input, generator)| {
+            let magnitude = *generator * Bn256Scalar::from(input.unsigned_abs() as u64);
+            if *input < 0 {
+                acc - magnitude
+            } else {
+                acc + magnitude
+            }
+        },
+    );
+    assert_eq!(
+        committer.signed_vector_commit(&message, &blinding_factor),
+        expected
+    );
+}
+
+#[test]
+fn test_heuristic_independence_check() {
+    use crate::entropy::test_seed;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    let mut rng = ChaCha20Rng::from_seed(test_seed("test_heuristic_independence_check").bytes());
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    assert!(committer.heuristic_independence_check(100, &mut rng));
+
+    let mut generators = committer.generators.clone();
+    generators[7] = generators[2];
+    let duplicated = PedersenCommitter::from_parts(generators, committer.blinding_generator);
+    assert!(!duplicated.heuristic_independence_check(100, &mut rng));
+
+    let mut generators = committer.generators.clone();
+    generators[3] = -committer.blinding_generator;
+    let negated = PedersenCommitter::from_parts(generators, committer.blinding_generator);
+    assert!(!negated.heuristic_independence_check(100, &mut rng));
+
+    let identity_blinding =
+        PedersenCommitter::from_parts(committer.generators.clone(), Bn256Point::zero());
+    assert!(!identity_blinding.heuristic_independence_check(100, &mut rng));
+
+    // --- With no message generators, only the blinding generator is checked ---
+    let blinding_only = PedersenCommitter::from_parts(vec![], committer.blinding_generator);
+    assert!(blinding_only.heuristic_independence_check(100, &mut rng));
+    let identity_only = PedersenCommitter::<Bn256Point>::from_parts(vec![], Bn256Point::zero());
+    assert!(!identity_only.heuristic_independence_check(100, &mut rng));
+}
+
+#[test]
+fn test_msm_path_matches_doublings_path() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let blinding_factor = Bn256Scalar::from(123u64);
+    for len in [0, 1, MSM_THRESHOLD - 1, MSM_THRESHOLD, 1 << LOG_NUM_COLS] {
+        let message: Vec<u8> = (0..len).map(|i| (i * 101 % 256) as u8).collect();
+        let reference = committer.vector_commit_with_doublings(&message, &blinding_factor);
+        assert_eq!(
+            committer.vector_commit_with_msm(&message, &blinding_factor),
+            reference
+        );
+        assert_eq!(
+            committer.vector_commit(&message, &blinding_factor),
+            reference
+        );
+        // --- and both agree with the curve's MSM over the elements as scalars ---
+        let scalar_message: Vec<Bn256Scalar> = message
+            .iter()
+            .map(|element| Bn256Scalar::from(*element as u64))
+            .collect();
+        assert_eq!(
+            Bn256Point::msm(&scalar_message, &committer.generators[..len])
+                + committer.blinding_generator * blinding_factor,
+            reference
+        );
+    }
+    // --- every bucket is used ---
+    let message: Vec<u8> = (0..=255).collect();
+    assert_eq!(
+        committer.vector_commit_with_msm(&message, &blinding_factor),
+        committer.vector_commit_with_doublings(&message, &blinding_factor)
+    );
+}
+
+#[test]
+fn test_comb_commit_matches_vector_commit() {
+    use ark_ff::UniformRand;
+
+    let mut rng = rand::thread_rng();
+    for window_bits in [1, 3, 8] {
+        let committer: PedersenCommitter<Bn256Point> =
+            PedersenCommitter::new(12, PUBLIC_STRING).precompute_comb(window_bits);
+        let blinding_factor = Bn256Scalar::rand(&mut rng);
+
+        // --- u8 messages commit as with vector_commit ---
+        let message: Vec<u8> = (0..12).map(|_| rng.gen()).collect();
+        let scalar_message: Vec<Bn256Scalar> = message
+            .iter()
+            .map(|element| Bn256Scalar::from(*element as u64))
+            .collect();
+        assert_eq!(
+            committer.vector_commit_comb(&scalar_message, &blinding_factor),
+            committer.vector_commit(&message, &blinding_factor)
+        );
+
+        // --- random full-width scalars, and messages shorter than the generators ---
+        let scalar_message: Vec<Bn256Scalar> =
+            (0..9).map(|_| Bn256Scalar::rand(&mut rng)).collect();
+        let expected = Bn256Point::msm(&scalar_message, &committer.generators[..9])
+            + committer.blinding_generator * blinding_factor;
+        assert_eq!(
+            committer.vector_commit_comb(&scalar_message, &blinding_factor),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_windowed_commit_matches_vector_commit() {
+    use ark_ff::UniformRand;
+
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(12, PUBLIC_STRING);
+    for window_bits in [1, 4, 7] {
+        let table = committer.build_window_table(window_bits);
+        assert_eq!(table.len(), 12);
+        assert_eq!(table.window_bits(), window_bits);
+        for _ in 0..4 {
+            let blinding_factor = Bn256Scalar::rand(&mut rng);
+
+            // --- u8 messages commit as with vector_commit ---
+            let message: Vec<u8> = (0..12).map(|_| rng.gen()).collect();
+            let scalar_message: Vec<Bn256Scalar> = message
+                .iter()
+                .map(|element| Bn256Scalar::from(*element as u64))
+                .collect();
+            assert_eq!(
+                committer.vector_commit_windowed(&scalar_message, &blinding_factor, &table),
+                committer.vector_commit(&message, &blinding_factor)
+            );
+
+            // --- random full-width scalars, and messages shorter than the generators ---
+            let scalar_message: Vec<Bn256Scalar> =
+                (0..9).map(|_| Bn256Scalar::rand(&mut rng)).collect();
+            assert_eq!(
+                committer.vector_commit_windowed(&scalar_message, &blinding_factor, &table),
+                committer.scalar_vector_commit(&scalar_message, &blinding_factor)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_i8_vector_commit() {
+    use std::ops::Neg;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let blinding = Bn256Scalar::from(11u64);
+    let expected = committer.generators[0] * Bn256Scalar::from(5u64).neg()
+        + committer.generators[1] * Bn256Scalar::from(7u64)
+        + committer.generators[2] * Bn256Scalar::from(128u64).neg()
+        + committer.generators[3] * Bn256Scalar::from(127u64)
+        + committer.blinding_generator * blinding;
+    assert_eq!(
+        committer.i8_vector_commit(&[-5, 7, -128, 127], &blinding),
+        expected
+    );
+
+    // --- Same as committing to the signed values as scalars, or by sign and magnitude ---
+    let scalar_message = [-5i8, 7, -128, 127]
+        .iter()
+        .map(|value| {
+            let magnitude = Bn256Scalar::from(value.unsigned_abs() as u64);
+            if *value < 0 {
+                magnitude.neg()
+            } else {
+                magnitude
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        committer.scalar_vector_commit(&scalar_message, &blinding),
+        expected
+    );
+    assert_eq!(
+        committer.integer_vector_commit(
+            &[5u8, 7, 128, 127],
+            &[true, false, true, false],
+            &blinding
+        ),
+        expected
+    );
+
+    // --- Magnitudes wider than a byte go through scalar multiplication ---
+    let expected = committer.generators[0] * Bn256Scalar::from(5u64).neg()
+        + committer.generators[1] * Bn256Scalar::from(7u64)
+        + committer.generators[2] * Bn256Scalar::from(40_000u64).neg()
+        + committer.generators[3] * Bn256Scalar::from(1u64 << 40)
+        + committer.blinding_generator * blinding;
+    assert_eq!(
+        committer.integer_vector_commit(
+            &[5u64, 7, 40_000, 1 << 40],
+            &[true, false, true, false],
+            &blinding
+        ),
+        expected
+    );
+}
+
+#[test]
+fn test_parallel_doublings_match_serial() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(64, PUBLIC_STRING);
+    let serial: Vec<Vec<Bn256Point>> = committer
+        .generators
+        .iter()
+        .map(|gen| precompute_doublings(*gen, U8_BITWIDTH))
+        .collect();
+    assert_eq!(committer.generator_doublings, serial);
+}
+
+#[test]
+fn test_scalar_vector_commit_matches_vector_commit() {
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(200, PUBLIC_STRING);
+    let blinding = Bn256Scalar::from(rng.gen::<u64>());
+    // --- One message below and one above MSM_THRESHOLD ---
+    for len in [17, 200] {
+        let message: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let scalars: Vec<Bn256Scalar> = message.iter().map(|x| Bn256Scalar::from(*x)).collect();
+        assert_eq!(
+            committer.vector_commit(&message, &blinding),
+            committer.scalar_vector_commit(&scalars, &blinding)
+        );
+    }
+
+    let scalar = -Bn256Scalar::from(3u64);
+    assert_eq!(
+        committer.scalar_commit(&scalar, &blinding),
+        committer.scalar_vector_commit(&[scalar], &blinding)
+    );
+}
+
+#[test]
+fn test_u16_and_u32_vector_commit() {
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let blinding = Bn256Scalar::from(rng.gen::<u64>());
+
+    // --- Wide elements of the default committer go through scalar multiplication, those of the
+    // committers with depth-16 and depth-32 tables through the tables ---
+    let committer_16: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new_with_bitwidth(8, PUBLIC_STRING, 16).unwrap();
+    let committer_32: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new_with_bitwidth(8, PUBLIC_STRING, 32).unwrap();
+
+    let message: Vec<u16> = vec![0, 1, 255, 256, 4097, u16::MAX, rng.gen(), rng.gen()];
+    let scalars: Vec<Bn256Scalar> = message.iter().map(|x| Bn256Scalar::from(*x)).collect();
+    let expected = committer.scalar_vector_commit(&scalars, &blinding);
+    assert_eq!(committer.u16_vector_commit(&message, &blinding), expected);
+    assert_eq!(
+        committer_16.u16_vector_commit(&message, &blinding),
+        expected
+    );
+
+    let message: Vec<u32> = vec![0, 255, 256, 65_536, 1 << 31, u32::MAX, rng.gen(), rng.gen()];
+    let scalars: Vec<Bn256Scalar> = message.iter().map(|x| Bn256Scalar::from(*x)).collect();
+    let expected = committer.scalar_vector_commit(&scalars, &blinding);
+    assert_eq!(committer.u32_vector_commit(&message, &blinding), expected);
+    assert_eq!(
+        committer_32.u32_vector_commit(&message, &blinding),
+        expected
+    );
+}
+
+#[test]
+fn test_evm_registry_round_trip() {
+    use crate::error::HyraxError;
+
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let registry_bytes = generators_to_evm_registry_bytes(&committer);
+    assert_eq!(registry_bytes.len(), 5 * 64);
+
+    let (generators, blinding_generator) =
+        generators_from_evm_registry_bytes::<Bn256Point>(&registry_bytes).unwrap();
+    assert_eq!(generators, committer.generators);
+    assert_eq!(blinding_generator, committer.blinding_generator);
+    let loaded = committer_from_evm_registry_bytes::<Bn256Point>(&registry_bytes).unwrap();
+    let blinding = Bn256Scalar::from(9u64);
+    assert_eq!(
+        loaded.vector_commit(&[1, 2, 3, 4], &blinding),
+        committer.vector_commit(&[1, 2, 3, 4], &blinding)
+    );
+
+    // --- The BN254 generator (1, 2) in the EVM encoding ---
+    let mut evm_generator = [0u8; 64];
+    evm_generator[31] = 1;
+    evm_generator[63] = 2;
+    assert_eq!(
+        generators_from_evm_registry_bytes::<Bn256Point>(&evm_generator),
+        Ok((vec![], Bn256Point::generator()))
+    );
+
+    // --- Malformed registries ---
+    assert_eq!(
+        generators_from_evm_registry_bytes::<Bn256Point>(&registry_bytes[..100]),
+        Err(HyraxError::InvalidByteLength {
+            expected: 128,
+            got: 100
+        })
+    );
+    let mut off_curve = registry_bytes.clone();
+    off_curve[64 + 63] ^= 1;
+    assert_eq!(
+        generators_from_evm_registry_bytes::<Bn256Point>(&off_curve),
+        Err(HyraxError::PointNotOnCurve)
+    );
+    assert_eq!(
+        generators_from_evm_registry_bytes::<Bn256Point>(&[0u8; 64]),
+        Err(HyraxError::PointNotOnCurve)
+    );
+}
+
+#[test]
+fn test_scalar_commit_identity() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(3, PUBLIC_STRING);
+    let zero = Bn256Scalar::from(0u64);
+    assert_eq!(
+        committer.scalar_commit(&zero, &zero),
+        <Bn256Point as PrimeOrderCurve>::zero()
+    );
+    let scalar = Bn256Scalar::from(23u64);
+    assert_eq!(
+        committer.scalar_commit(&scalar, &zero),
+        committer.generators[0] * scalar
+    );
+}
+
+#[test]
+fn test_scalar_commit_blinding_independence() {
+    // --- The blinding contribution does not depend on the committed scalar ---
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(3, PUBLIC_STRING);
+    let zero = Bn256Scalar::from(0u64);
+    let blinding = Bn256Scalar::from(77u64);
+    for scalar in [0u64, 1, 5, 1 << 40].map(Bn256Scalar::from) {
+        assert_eq!(
+            committer.scalar_commit(&scalar, &blinding) - committer.scalar_commit(&scalar, &zero),
+            committer.blinding_generator * blinding
+        );
+    }
+}
+
+#[test]
+fn test_scalar_commit_blinding_factor_dependence() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(3, PUBLIC_STRING);
+    let scalar = Bn256Scalar::from(5u64);
+    assert_ne!(
+        committer.scalar_commit(&scalar, &Bn256Scalar::from(1u64)),
+        committer.scalar_commit(&scalar, &Bn256Scalar::from(2u64))
+    );
+}
+
+#[test]
+fn test_split_at() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    for mid in 0..=8 {
+        let (left, right) = committer.split_at(mid);
+        assert_eq!(left.generators, committer.generators[..mid]);
+        assert_eq!(right.generators, committer.generators[mid..]);
+        assert_eq!(
+            left.generator_doublings,
+            committer.generator_doublings[..mid]
+        );
+        assert_eq!(
+            right.generator_doublings,
+            committer.generator_doublings[mid..]
+        );
+        assert_eq!(left.blinding_generator, committer.blinding_generator);
+        assert_eq!(right.blinding_generator, committer.blinding_generator);
+    }
+    // --- The edge cases give an empty and a full half ---
+    let (empty, full) = committer.split_at(0);
+    assert!(empty.generators.is_empty());
+    assert_eq!(full.generators, committer.generators);
+    let (full, empty) = committer.split_at(8);
+    assert_eq!(full.generators, committer.generators);
+    assert!(empty.generators.is_empty());
+}
+
+#[test]
+fn test_from_generators() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    let rebuilt = PedersenCommitter::from_generators(
+        committer.generators.clone(),
+        committer.blinding_generator,
+    );
+    assert_eq!(rebuilt.generator_doublings, committer.generator_doublings);
+
+    // --- The halves of a rebuilt committer are the halves of the original ---
+    let (left, right) = rebuilt.split_at(5);
+    let (expected_left, expected_right) = committer.split_at(5);
+    let blinding = Bn256Scalar::from(3u64);
+    assert_eq!(
+        left.vector_commit(&[1, 2, 3, 4, 5], &blinding),
+        expected_left.vector_commit(&[1, 2, 3, 4, 5], &blinding)
+    );
+    assert_eq!(
+        right.vector_commit(&[6, 7, 8], &blinding),
+        expected_right.vector_commit(&[6, 7, 8], &blinding)
+    );
+}
+
+#[test]
+fn test_split_commitments_sum_to_full_commitment() {
+    let mut rng = rand::thread_rng();
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(8, PUBLIC_STRING).precompute_comb(4);
+    let message: Vec<u8> = (0..8).map(|_| rng.gen()).collect();
+    let (left_blinding, right_blinding) = (
+        Bn256Scalar::from(rng.gen::<u64>()),
+        Bn256Scalar::from(rng.gen::<u64>()),
+    );
+    let full_commit = committer.vector_commit(&message, &(left_blinding + right_blinding));
+
+    let (left, right) = committer.split_at(3);
+    assert_eq!(
+        left.vector_commit(&message[..3], &left_blinding)
+            + right.vector_commit(&message[3..], &right_blinding),
+        full_commit
+    );
+    // --- The comb tables are split along with the generators ---
+    let scalars: Vec<Bn256Scalar> = message.iter().map(|x| Bn256Scalar::from(*x)).collect();
+    assert_eq!(
+        left.vector_commit_comb(&scalars[..3], &left_blinding)
+            + right.vector_commit_comb(&scalars[3..], &right_blinding),
+        full_commit
+    );
+}
+
+#[test]
+fn test_new_with_bitwidth() {
+    // --- BN254's scalar field has 254 bits ---
+    assert!(matches!(
+        PedersenCommitter::<Bn256Point>::new_with_bitwidth(4, PUBLIC_STRING, 256),
+        Err(HyraxError::BitwidthExceedsField {
+            bitwidth: 256,
+            field_bits: 254
+        })
+    ));
+    assert!(PedersenCommitter::<Bn256Point>::new_with_bitwidth(4, PUBLIC_STRING, 254).is_err());
+    assert!(PedersenCommitter::<Bn256Point>::new_with_bitwidth(4, PUBLIC_STRING, 253).is_ok());
+
+    // --- Wide tables commit to the same integers as scalar multiplication ---
+    let wide = PedersenCommitter::<Bn256Point>::new_with_bitwidth(4, PUBLIC_STRING, 32).unwrap();
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    assert_eq!(wide.generators, committer.generators);
+    assert!(wide
+        .generator_doublings
+        .iter()
+        .all(|table| table.len() == 32));
+    let message = [7u32, 300, 70_000, u32::MAX];
+    let blinding = Bn256Scalar::from(5u64);
+    assert_eq!(
+        wide.u32_vector_commit(&message, &blinding),
+        committer.u32_vector_commit(&message, &blinding)
+    );
+    assert_eq!(
+        wide.integer_vector_commit(&[1u64 << 40], &[true], &blinding),
+        committer.integer_vector_commit(&[1u64 << 40], &[true], &blinding)
+    );
+    assert_eq!(
+        wide.vector_commit(&[1, 2, 3, 4], &blinding),
+        committer.vector_commit(&[1, 2, 3, 4], &blinding)
+    );
+}
+
+#[test]
+fn test_add_public_vector() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let blinding = Bn256Scalar::from(21u64);
+    let message: [u8; 4] = [1, 200, 255, 0];
+    let public: [u8; 4] = [9, 100, 255, 3];
+    let commitment = committer.vector_commit(&message, &blinding);
+
+    // --- Sums beyond u8::MAX are committed to as field elements ---
+    let sum = message
+        .iter()
+        .zip(public.iter())
+        .map(|(m, p)| Bn256Scalar::from(*m as u64 + *p as u64))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        add_public_vector(commitment, &public, &committer).unwrap(),
+        committer.scalar_vector_commit(&sum, &blinding)
+    );
+
+    // --- A shorter public vector adds to the first elements only ---
+    assert_eq!(
+        add_public_vector(commitment, &[1, 1], &committer).unwrap(),
+        committer.vector_commit(&[2, 201, 255, 0], &blinding)
+    );
+    assert_eq!(
+        add_public_vector(commitment, &[0; 5], &committer),
+        Err(HyraxError::MessageTooLong {
+            capacity: 4,
+            got: 5
+        })
+    );
+}
+
+#[test]
+fn test_committer_bytes_round_trip() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << LOG_NUM_COLS, PUBLIC_STRING);
+    let bytes = committer.to_bytes();
+    assert_eq!(
+        bytes.len(),
+        ((1 << LOG_NUM_COLS) + 1) * Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH
+    );
+
+    // --- The loaded committer matches the freshly sampled one, tables included ---
+    let loaded: PedersenCommitter<Bn256Point> = PedersenCommitter::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.generators, committer.generators);
+    assert_eq!(loaded.blinding_generator, committer.blinding_generator);
+    assert!(loaded.matches_public_string(PUBLIC_STRING));
+    let message: Vec<u8> = (0..(1 << LOG_NUM_COLS)).map(|i| (i % 251) as u8).collect();
+    let blinding = Bn256Scalar::from(5u64);
+    assert_eq!(
+        loaded.vector_commit_with_doublings(&message, &blinding),
+        committer.vector_commit_with_doublings(&message, &blinding)
+    );
+
+    // --- Generators of another public string do not match ---
+    let other: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "a different public string of at least 32 bytes");
+    assert!(
+        !PedersenCommitter::<Bn256Point>::from_bytes(&other.to_bytes())
+            .unwrap()
+            .matches_public_string(PUBLIC_STRING)
+    );
+
+    assert_eq!(
+        PedersenCommitter::<Bn256Point>::from_bytes(&bytes[1..]).err(),
+        Some(HyraxError::InvalidByteLength {
+            expected: bytes.len(),
+            got: bytes.len() - 1
+        })
+    );
+    assert!(PedersenCommitter::<Bn256Point>::from_bytes(&[]).is_err());
+}
+
+#[test]
+fn test_verify_blinding_generator_independence() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(8, PUBLIC_STRING);
+    assert!(committer.verify_blinding_generator_independence(PUBLIC_STRING));
+    assert!(!committer
+        .verify_blinding_generator_independence("a different public string of at least 32 bytes"));
+
+    // --- A blinding generator that is also a message generator ---
+    let mut generators = committer.generators.clone();
+    generators[3] = committer.blinding_generator;
+    let reused = PedersenCommitter::from_parts(generators, committer.blinding_generator);
+    assert!(!reused.verify_blinding_generator_independence(PUBLIC_STRING));
+
+    // --- A message generator as the blinding generator ---
+    let swapped =
+        PedersenCommitter::from_parts(committer.generators.clone(), committer.generators[0]);
+    assert!(!swapped.verify_blinding_generator_independence(PUBLIC_STRING));
+}
+
+#[test]
+fn test_generators_bytes_round_trip() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new_with_bitwidth(64, PUBLIC_STRING, 16).unwrap();
+    let bytes = committer.to_generators_bytes();
+    assert_eq!(
+        bytes.len(),
+        64 * Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH
+    );
+
+    let loaded: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::from_generators_bytes(&bytes, committer.blinding_generator, 16).unwrap();
+    assert_eq!(loaded.generators, committer.generators);
+    assert_eq!(loaded.blinding_generator, committer.blinding_generator);
+    assert_eq!(loaded.generator_doublings, committer.generator_doublings);
+    assert_eq!(loaded.doublings_bit_order, committer.doublings_bit_order);
+    assert_eq!(loaded.to_generators_bytes(), bytes);
+    // --- the same bytes as `to_bytes` without the blinding generator ---
+    assert_eq!(
+        committer.to_bytes()[Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH..],
+        bytes[..]
+    );
+
+    let blinding = Bn256Scalar::from(77u64);
+    let message: Vec<u8> = (0..64).map(|i| (i * 3 % 256) as u8).collect();
+    assert_eq!(
+        loaded.vector_commit(&message, &blinding),
+        committer.vector_commit(&message, &blinding)
+    );
+    let wide_message: Vec<u16> = (0..64).map(|i| (i * 1021 % 65536) as u16).collect();
+    assert_eq!(
+        loaded.u16_vector_commit(&wide_message, &blinding),
+        committer.u16_vector_commit(&wide_message, &blinding)
+    );
+
+    assert_eq!(
+        PedersenCommitter::<Bn256Point>::from_generators_bytes(
+            &bytes,
+            committer.blinding_generator,
+            254
+        )
+        .err(),
+        Some(HyraxError::BitwidthExceedsField {
+            bitwidth: 254,
+            field_bits: 254
+        })
+    );
+}
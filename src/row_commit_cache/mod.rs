@@ -0,0 +1,147 @@
+use crate::curves::PrimeOrderCurve;
+use crate::pedersen::PedersenCommitter;
+use crate::scalar_serde::serialize_scalars;
+use blake2::{Blake2s256, Digest};
+use zeroize::Zeroize;
+
+#[cfg(test)]
+pub mod tests;
+
+/// A bounded, least-recently-used cache of row commitments, for a server (e.g. the audit service)
+/// that re-verifies overlapping sets of the same captures: a row that was already committed to
+/// with the same blinding factor is not recommitted to.
+///
+/// SERVER-SIDE ONLY. Each entry is keyed by the Blake2s256 digest of the row bytes concatenated
+/// with the canonical bytes of its blinding factor, i.e. by material derived from the iris code
+/// and the secret blinding factor. The cache therefore lives only in the caller's memory; it is
+/// deliberately neither `Clone` nor serializable, and its keys are zeroized when it is dropped.
+/// It must never be shipped to or persisted on a device.
+///
+/// The cache owns its committer, so that a cached commitment can never be returned for a
+/// different committer than the one that computed it.
+pub struct RowCommitCache<C: PrimeOrderCurve> {
+    vector_committer: PedersenCommitter<C>,
+    capacity: usize,
+    // Allocated to `capacity` up front and never grown, so that no copy of a key is left behind
+    // in a freed buffer by a reallocation.
+    entries: Vec<CacheEntry<C>>,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+struct CacheEntry<C: PrimeOrderCurve> {
+    key: [u8; 32],
+    commitment: C,
+    last_used: u64,
+}
+
+impl<C: PrimeOrderCurve> RowCommitCache<C> {
+    /// Creates an empty cache holding at most `capacity` row commitments under `vector_committer`.
+    /// Lookups scan the entries, which is negligible next to a row commitment for capacities in
+    /// the thousands.
+    pub fn new(vector_committer: PedersenCommitter<C>, capacity: usize) -> Self {
+        Self {
+            vector_committer,
+            capacity,
+            entries: Vec::with_capacity(capacity),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The committer that the cached commitments were computed with.
+    pub fn vector_committer(&self) -> &PedersenCommitter<C> {
+        &self.vector_committer
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of row commitments currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of calls to `vector_commit` answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of calls to `vector_commit` that computed the commitment.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Same as `self.vector_committer().vector_commit(row, blinding_factor)`, returning the
+    /// cached commitment if there is one and caching the computed one otherwise, evicting the
+    /// least recently used entry if the cache is full.
+    pub fn vector_commit(&mut self, row: &[u8], blinding_factor: &C::Scalar) -> C {
+        self.clock += 1;
+        let mut key = Self::key(row, blinding_factor);
+
+        let commitment = match self.entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => {
+                self.hits += 1;
+                entry.last_used = self.clock;
+                entry.commitment
+            }
+            None => {
+                self.misses += 1;
+                let commitment = self.vector_committer.vector_commit(row, blinding_factor);
+                let entry = CacheEntry {
+                    key,
+                    commitment,
+                    last_used: self.clock,
+                };
+                if self.entries.len() < self.capacity {
+                    self.entries.push(entry);
+                } else if let Some(least_recently_used) =
+                    self.entries.iter_mut().min_by_key(|entry| entry.last_used)
+                {
+                    // --- Overwritten in place, so the evicted key does not outlive the entry ---
+                    *least_recently_used = entry;
+                }
+                commitment
+            }
+        };
+        key.zeroize();
+        commitment
+    }
+
+    /// Empties the cache, zeroizing its keys. The hit and miss counts are kept.
+    pub fn clear(&mut self) {
+        self.zeroize_keys();
+        self.entries.clear();
+    }
+
+    // Blake2s256 of the row bytes followed by the canonical bytes of the blinding factor.
+    fn key(row: &[u8], blinding_factor: &C::Scalar) -> [u8; 32] {
+        let mut blinding_bytes = serialize_scalars::<C>(std::slice::from_ref(blinding_factor));
+        let key = Blake2s256::new()
+            .chain_update(row)
+            .chain_update(&blinding_bytes)
+            .finalize()
+            .into();
+        blinding_bytes.zeroize();
+        key
+    }
+
+    fn zeroize_keys(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.key.zeroize();
+        }
+    }
+}
+
+impl<C: PrimeOrderCurve> Drop for RowCommitCache<C> {
+    fn drop(&mut self) {
+        self.zeroize_keys();
+    }
+}
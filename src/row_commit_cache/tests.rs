@@ -0,0 +1,125 @@
+use super::*;
+use crate::entropy::test_seed;
+use crate::iriscode_commit::{compute_commitments, verify_commitment_with_cache, PUBLIC_STRING};
+use ark_bn254::G1Projective as Bn256Point;
+use ark_ff::UniformRand;
+
+type Scalar = <Bn256Point as PrimeOrderCurve>::Scalar;
+
+fn committer() -> PedersenCommitter<Bn256Point> {
+    PedersenCommitter::new(1 << 3, PUBLIC_STRING)
+}
+
+#[test]
+fn test_hits_and_misses() {
+    let mut rng = rand::thread_rng();
+    let mut cache = RowCommitCache::new(committer(), 4);
+    let row = [1u8, 2, 3, 4, 5, 6, 7, 8];
+    let blinding_factor = Scalar::rand(&mut rng);
+    let expected = committer().vector_commit(&row, &blinding_factor);
+
+    assert_eq!(cache.vector_commit(&row, &blinding_factor), expected);
+    assert_eq!((cache.hits(), cache.misses()), (0, 1));
+    assert_eq!(cache.vector_commit(&row, &blinding_factor), expected);
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+    // --- Changing either the row or the blinding factor is a miss ---
+    let other_blinding_factor = Scalar::rand(&mut rng);
+    assert_eq!(
+        cache.vector_commit(&row, &other_blinding_factor),
+        committer().vector_commit(&row, &other_blinding_factor)
+    );
+    let other_row = [8u8, 7, 6, 5, 4, 3, 2, 1];
+    assert_eq!(
+        cache.vector_commit(&other_row, &blinding_factor),
+        committer().vector_commit(&other_row, &blinding_factor)
+    );
+    assert_eq!((cache.hits(), cache.misses()), (1, 3));
+    assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_eviction_is_least_recently_used() {
+    let mut rng = rand::thread_rng();
+    let mut cache = RowCommitCache::new(committer(), 2);
+    let rows = [[0u8; 8], [1u8; 8], [2u8; 8]];
+    let blinding_factors = (0..3).map(|_| Scalar::rand(&mut rng)).collect::<Vec<_>>();
+    let expected = rows
+        .iter()
+        .zip(blinding_factors.iter())
+        .map(|(row, blind)| committer().vector_commit(row, blind))
+        .collect::<Vec<_>>();
+
+    let _ = cache.vector_commit(&rows[0], &blinding_factors[0]);
+    let _ = cache.vector_commit(&rows[1], &blinding_factors[1]);
+    // --- Touch row 0, so that row 1 is evicted to make room for row 2 ---
+    let _ = cache.vector_commit(&rows[0], &blinding_factors[0]);
+    assert_eq!(
+        cache.vector_commit(&rows[2], &blinding_factors[2]),
+        expected[2]
+    );
+    assert_eq!(cache.len(), 2);
+    assert_eq!((cache.hits(), cache.misses()), (1, 3));
+
+    assert_eq!(
+        cache.vector_commit(&rows[0], &blinding_factors[0]),
+        expected[0]
+    );
+    assert_eq!((cache.hits(), cache.misses()), (2, 3));
+    assert_eq!(
+        cache.vector_commit(&rows[1], &blinding_factors[1]),
+        expected[1]
+    );
+    assert_eq!((cache.hits(), cache.misses()), (2, 4));
+}
+
+#[test]
+fn test_verify_commitment_with_cache() {
+    let data = (0..40).map(|i| i as u8).collect::<Vec<_>>();
+    let output = compute_commitments(&data, &committer(), test_seed("row_commit_cache").bytes());
+    let output = output.unwrap();
+    let mut cache = RowCommitCache::new(committer(), 16);
+
+    assert!(verify_commitment_with_cache(
+        &data,
+        &output.commitment,
+        &output.blinding_factors,
+        &mut cache
+    ));
+    assert_eq!((cache.hits(), cache.misses()), (0, 8));
+    assert!(verify_commitment_with_cache(
+        &data,
+        &output.commitment,
+        &output.blinding_factors,
+        &mut cache
+    ));
+    assert_eq!((cache.hits(), cache.misses()), (8, 8));
+
+    // --- A flipped byte misses the cache and fails to verify ---
+    let mut corrupted = data.clone();
+    corrupted[3] ^= 1;
+    assert!(!verify_commitment_with_cache(
+        &corrupted,
+        &output.commitment,
+        &output.blinding_factors,
+        &mut cache
+    ));
+    assert_eq!(cache.misses(), 9);
+}
+
+#[test]
+fn test_keys_are_zeroized() {
+    let mut rng = rand::thread_rng();
+    let mut cache = RowCommitCache::new(committer(), 4);
+    for row in [[0u8; 8], [1u8; 8]] {
+        let _ = cache.vector_commit(&row, &Scalar::rand(&mut rng));
+    }
+    assert!(cache.entries.iter().all(|entry| entry.key != [0u8; 32]));
+
+    // --- `Drop` calls `zeroize_keys` ---
+    cache.zeroize_keys();
+    assert!(cache.entries.iter().all(|entry| entry.key == [0u8; 32]));
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,41 @@
+use crate::curves::{canonical_from_le_bytes, PrimeOrderCurve};
+use crate::error::HyraxError;
+use ark_ff::BigInteger;
+use ark_ff::PrimeField;
+
+#[cfg(test)]
+pub mod tests;
+
+/// Serializes the scalars as the concatenation of their little-endian canonical representations,
+/// each `C::SCALAR_ELEM_BYTEWIDTH` bytes wide.
+/// Post: result.len() == scalars.len() * C::SCALAR_ELEM_BYTEWIDTH
+pub fn serialize_scalars<C: PrimeOrderCurve>(scalars: &[C::Scalar]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(scalars.len() * C::SCALAR_ELEM_BYTEWIDTH);
+    for scalar in scalars {
+        let scalar_bytes = scalar.into_bigint().to_bytes_le();
+        debug_assert_eq!(scalar_bytes.len(), C::SCALAR_ELEM_BYTEWIDTH);
+        bytes.extend(scalar_bytes);
+    }
+    bytes
+}
+
+/// Inverse of `serialize_scalars`.
+/// Errors with `HyraxError::InvalidScalarBufferLength` if the buffer does not hold a whole number
+/// of scalars (e.g. because it was truncated), and with `HyraxError::NonCanonicalScalar` holding
+/// the index of the first scalar that is not reduced modulo the field order, so that each list of
+/// scalars has a single serialization.
+pub fn deserialize_scalars<C: PrimeOrderCurve>(bytes: &[u8]) -> Result<Vec<C::Scalar>, HyraxError> {
+    if bytes.len() % C::SCALAR_ELEM_BYTEWIDTH != 0 {
+        return Err(HyraxError::InvalidScalarBufferLength {
+            len: bytes.len(),
+            scalar_width: C::SCALAR_ELEM_BYTEWIDTH,
+        });
+    }
+    bytes
+        .chunks(C::SCALAR_ELEM_BYTEWIDTH)
+        .enumerate()
+        .map(|(index, scalar_bytes)| {
+            canonical_from_le_bytes(scalar_bytes).map_err(|_| HyraxError::NonCanonicalScalar(index))
+        })
+        .collect()
+}
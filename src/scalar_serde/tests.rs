@@ -0,0 +1,47 @@
+use super::*;
+use crate::entropy::test_seed;
+use ark_bn254::Fr as Bn256Scalar;
+use ark_bn254::G1Projective as Bn256Point;
+use ark_ff::UniformRand;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[test]
+fn test_scalar_round_trip() {
+    let mut rng = ChaCha20Rng::from_seed(test_seed("scalar round trip").bytes());
+    let scalars: Vec<Bn256Scalar> = (0..7).map(|_| Bn256Scalar::rand(&mut rng)).collect();
+    let bytes = serialize_scalars::<Bn256Point>(&scalars);
+    assert_eq!(bytes.len(), 7 * Bn256Point::SCALAR_ELEM_BYTEWIDTH);
+    assert_eq!(deserialize_scalars::<Bn256Point>(&bytes), Ok(scalars));
+}
+
+#[test]
+fn test_truncated_scalar_buffer_errors() {
+    let scalars = vec![Bn256Scalar::from(1u64), Bn256Scalar::from(2u64)];
+    let bytes = serialize_scalars::<Bn256Point>(&scalars);
+    assert_eq!(
+        deserialize_scalars::<Bn256Point>(&bytes[..bytes.len() - 1]),
+        Err(HyraxError::InvalidScalarBufferLength {
+            len: 63,
+            scalar_width: 32
+        })
+    );
+}
+
+#[test]
+fn test_non_canonical_scalar_errors() {
+    let scalars = vec![Bn256Scalar::from(1u64), Bn256Scalar::from(2u64)];
+    let mut bytes = serialize_scalars::<Bn256Point>(&scalars);
+    // --- The modulus itself, which `from_le_bytes_mod_order` would reduce to 0 ---
+    let modulus = Bn256Scalar::MODULUS.to_bytes_le();
+    bytes[32..].copy_from_slice(&modulus);
+    assert_eq!(
+        deserialize_scalars::<Bn256Point>(&bytes),
+        Err(HyraxError::NonCanonicalScalar(1))
+    );
+    bytes[32..].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        deserialize_scalars::<Bn256Point>(&bytes),
+        Err(HyraxError::NonCanonicalScalar(1))
+    );
+}
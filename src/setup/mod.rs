@@ -0,0 +1,122 @@
+use crate::curves::PrimeOrderCurve;
+use crate::error::HyraxError;
+use crate::pedersen::{PedersenCommitter, U8_BITWIDTH};
+use blake2::{Blake2s256, Digest};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+pub mod tests;
+
+/// The canonical artifact for pinning the Pedersen setup of a deployment: the
+/// generators (blinding generator first, then the message generators, each
+/// compressed) together with everything needed to re-derive and check them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetupManifest {
+    pub public_string: String,
+    pub curve: String,
+    /// The committer's `PedersenCommitter::bitwidth`, which `import_setup_manifest` rebuilds.
+    pub bitwidth: usize,
+    pub generator_bytes: Vec<u8>,
+    /// Blake2s256 digest of all of the fields above, see `SetupManifest::compute_digest`.
+    pub digest: [u8; 32],
+}
+
+impl SetupManifest {
+    /// Deterministic binary serialization of the manifest.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap()
+    }
+
+    /// Parses a manifest serialized with `to_bytes`. This only checks the encoding;
+    /// use `import_setup_manifest` to validate the contents.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HyraxError> {
+        bincode::deserialize(bytes).map_err(|err| HyraxError::InvalidSetupManifest(err.to_string()))
+    }
+
+    /// The digest of the manifest's contents (every field except `digest` itself).
+    pub fn compute_digest(&self) -> [u8; 32] {
+        let contents = (
+            &self.public_string,
+            &self.curve,
+            self.bitwidth,
+            &self.generator_bytes,
+        );
+        let mut hasher = Blake2s256::new();
+        hasher.update(bincode::serialize(&contents).unwrap());
+        hasher.finalize().into()
+    }
+}
+
+/// Exports the setup of `committer`, which must have been sampled from `public_string`
+/// (this is not checked here; `import_setup_manifest` re-derives and checks the generators).
+pub fn export_setup_manifest<C: PrimeOrderCurve>(
+    committer: &PedersenCommitter<C>,
+    public_string: &str,
+) -> SetupManifest {
+    let generator_bytes = std::iter::once(&committer.blinding_generator)
+        .chain(committer.generators.iter())
+        .flat_map(|generator| generator.to_bytes_compressed())
+        .collect();
+    let mut manifest = SetupManifest {
+        public_string: public_string.to_string(),
+        curve: C::CURVE_NAME.to_string(),
+        bitwidth: committer.bitwidth(),
+        generator_bytes,
+        digest: [0; 32],
+    };
+    manifest.digest = manifest.compute_digest();
+    manifest
+}
+
+/// Validates `manifest` and returns the committer it describes, with doublings tables of the
+/// manifest's bitwidth (as built by `PedersenCommitter::new_with_bitwidth`). The digest, curve
+/// and bitwidth are checked, and the generators are re-derived from the public string and
+/// compared against the generator bytes.
+/// Errors with `HyraxError::BitwidthExceedsField` if the bitwidth is too wide for the curve.
+pub fn import_setup_manifest<C: PrimeOrderCurve>(
+    manifest: &SetupManifest,
+) -> Result<PedersenCommitter<C>, HyraxError> {
+    if manifest.compute_digest() != manifest.digest {
+        return Err(HyraxError::SetupManifestDigestMismatch);
+    }
+    if manifest.curve != C::CURVE_NAME {
+        return Err(HyraxError::InvalidSetupManifest(format!(
+            "manifest is for curve {}, expected {}",
+            manifest.curve,
+            C::CURVE_NAME
+        )));
+    }
+    if manifest.bitwidth < U8_BITWIDTH {
+        return Err(HyraxError::InvalidSetupManifest(format!(
+            "manifest has bitwidth {}, expected at least {}",
+            manifest.bitwidth, U8_BITWIDTH
+        )));
+    }
+    if manifest.public_string.len() < 32 {
+        return Err(HyraxError::InvalidSetupManifest(
+            "public string is shorter than 32 bytes".to_string(),
+        ));
+    }
+    let num_points = manifest.generator_bytes.len() / C::COMPRESSED_CURVE_POINT_BYTEWIDTH;
+    if num_points == 0 || manifest.generator_bytes.len() % C::COMPRESSED_CURVE_POINT_BYTEWIDTH != 0
+    {
+        return Err(HyraxError::InvalidSetupManifest(
+            "generator bytes do not hold a whole, nonzero number of points".to_string(),
+        ));
+    }
+
+    // --- Re-derive the generators and check them against the blob ---
+    let committer = PedersenCommitter::new_with_bitwidth(
+        num_points - 1,
+        &manifest.public_string,
+        manifest.bitwidth,
+    )?;
+    if export_setup_manifest(&committer, &manifest.public_string).generator_bytes
+        != manifest.generator_bytes
+    {
+        return Err(HyraxError::InvalidSetupManifest(
+            "generators do not match those derived from the public string".to_string(),
+        ));
+    }
+    Ok(committer)
+}
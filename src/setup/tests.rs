@@ -0,0 +1,95 @@
+use super::*;
+use crate::iriscode_commit::PUBLIC_STRING;
+use ark_bn254::G1Projective as Bn256Point;
+
+#[test]
+fn test_setup_manifest_round_trip() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(16, PUBLIC_STRING);
+    let manifest = export_setup_manifest(&committer, PUBLIC_STRING);
+    assert_eq!(
+        manifest.generator_bytes.len(),
+        17 * Bn256Point::COMPRESSED_CURVE_POINT_BYTEWIDTH
+    );
+
+    // --- Serialization is deterministic and round-trips ---
+    let manifest_bytes = manifest.to_bytes();
+    assert_eq!(
+        manifest_bytes,
+        export_setup_manifest(&committer, PUBLIC_STRING).to_bytes()
+    );
+    let parsed = SetupManifest::from_bytes(&manifest_bytes).unwrap();
+    assert_eq!(parsed, manifest);
+
+    let imported: PedersenCommitter<Bn256Point> = import_setup_manifest(&parsed).unwrap();
+    assert_eq!(imported.generators, committer.generators);
+    assert_eq!(imported.blinding_generator, committer.blinding_generator);
+}
+
+#[test]
+fn test_setup_manifest_round_trips_bitwidth() {
+    let committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new_with_bitwidth(4, PUBLIC_STRING, 16).unwrap();
+    let manifest = export_setup_manifest(&committer, PUBLIC_STRING);
+    assert_eq!(manifest.bitwidth, 16);
+
+    let imported: PedersenCommitter<Bn256Point> = import_setup_manifest(&manifest).unwrap();
+    assert_eq!(imported.bitwidth(), 16);
+    assert_eq!(export_setup_manifest(&imported, PUBLIC_STRING), manifest);
+
+    // --- A wide committer commits to wide integers the same way after the round trip ---
+    let message = [40_000u16, 3, 65_535, 1];
+    let blinding = <Bn256Point as PrimeOrderCurve>::Scalar::from(7u64);
+    assert_eq!(
+        imported.u16_vector_commit(&message, &blinding),
+        committer.u16_vector_commit(&message, &blinding)
+    );
+}
+
+#[test]
+fn test_import_rejects_invalid_bitwidth() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let manifest = export_setup_manifest(&committer, PUBLIC_STRING);
+    assert_eq!(manifest.bitwidth, U8_BITWIDTH);
+
+    // --- Narrower than the 8-bit tables every committer has ---
+    let mut narrow = manifest.clone();
+    narrow.bitwidth = 4;
+    narrow.digest = narrow.compute_digest();
+    assert!(matches!(
+        import_setup_manifest::<Bn256Point>(&narrow),
+        Err(HyraxError::InvalidSetupManifest(_))
+    ));
+
+    // --- Too wide for the scalar field ---
+    let mut wide = manifest.clone();
+    wide.bitwidth = 1000;
+    wide.digest = wide.compute_digest();
+    assert!(matches!(
+        import_setup_manifest::<Bn256Point>(&wide),
+        Err(HyraxError::BitwidthExceedsField { .. })
+    ));
+}
+
+#[test]
+fn test_import_rejects_tampered_manifest() {
+    let committer: PedersenCommitter<Bn256Point> = PedersenCommitter::new(4, PUBLIC_STRING);
+    let manifest = export_setup_manifest(&committer, PUBLIC_STRING);
+
+    // --- Contents changed without updating the digest ---
+    let mut tampered = manifest.clone();
+    tampered.generator_bytes[1] ^= 1;
+    assert_eq!(
+        import_setup_manifest::<Bn256Point>(&tampered).err(),
+        Some(HyraxError::SetupManifestDigestMismatch)
+    );
+
+    // --- Contents and digest both changed: the re-derived generators don't match ---
+    let other_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(4, "accountable magic something something");
+    let mut mislabelled = export_setup_manifest(&other_committer, PUBLIC_STRING);
+    mislabelled.digest = mislabelled.compute_digest();
+    assert!(matches!(
+        import_setup_manifest::<Bn256Point>(&mislabelled),
+        Err(HyraxError::InvalidSetupManifest(_))
+    ));
+}
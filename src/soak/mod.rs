@@ -0,0 +1,196 @@
+use crate::curves::PrimeOrderCurve;
+use crate::error::HyraxError;
+use crate::iriscode_commit::{
+    compute_commitments, compute_commitments_in_pool, pad_to_power_of_two, validate_input_len,
+    HyraxCommitmentOutput, MAX_INPUT_LEN, PUBLIC_STRING,
+};
+use crate::pedersen::PedersenCommitter;
+use itertools::Itertools;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+pub mod tests;
+
+/// Parameters for a soak run of the committer, used for hardware qualification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoakConfig {
+    /// Number of random images to commit to.
+    pub iterations: usize,
+    /// Length in bytes of each random image.
+    pub data_len: usize,
+    /// Log of the number of columns of the commitment matrix.
+    pub log_num_cols: usize,
+    /// Seed for the stream of random images and blinding factor seeds, so that a run can be replayed.
+    pub rng_seed: u64,
+    /// Re-verify against the naive scalar-multiplication reference every this many iterations
+    /// (0 disables the reference check).
+    pub reference_check_interval: usize,
+}
+
+/// The cross-checks performed in each soak iteration, against the row commitments of
+/// `compute_commitments`. These go through `vector_commit`, i.e. the bucket MSM for rows of at
+/// least `MSM_THRESHOLD` elements (such as the Orb's) and the precomputed doublings for shorter
+/// ones, so the `Doublings` check forces the other path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoakCheck {
+    /// Committing to the image on rayon's global pool and on a single thread gave different
+    /// outputs.
+    Serial,
+    /// Recomputing every row from the precomputed doublings (`vector_commit_with_doublings`)
+    /// disagreed with the commitment.
+    Doublings,
+    /// Recomputing every row with the curve's multi-scalar multiplication disagreed with the
+    /// commitment.
+    Msm,
+    /// Recomputing every row with naive scalar multiplications disagreed with the commitment.
+    Reference,
+}
+
+/// An iteration in which at least one cross-check failed, with everything needed to reproduce it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoakMismatch {
+    pub iteration: usize,
+    pub failed_checks: Vec<SoakCheck>,
+    pub data: Vec<u8>,
+    pub blinding_factor_seed: [u8; 32],
+}
+
+/// Summary of a soak run, meant to be emitted as JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoakReport {
+    pub iterations_completed: usize,
+    pub reference_checks: usize,
+    pub mismatches: Vec<SoakMismatch>,
+}
+
+/// Runs the soak loop described by `config` against a committer sampled from `PUBLIC_STRING`.
+/// Errors with `HyraxError::InvalidMatrixShape` if `1 << config.log_num_cols` overflows, and as
+/// `run_soak_with_committer` otherwise.
+pub fn run_soak<C: PrimeOrderCurve>(config: &SoakConfig) -> Result<SoakReport, HyraxError> {
+    let n_cols = u32::try_from(config.log_num_cols)
+        .ok()
+        .and_then(|log_num_cols| 1usize.checked_shl(log_num_cols))
+        .ok_or(HyraxError::InvalidMatrixShape {
+            data_len: config.data_len,
+            n_cols: usize::MAX,
+        })?;
+    let vector_committer: PedersenCommitter<C> = PedersenCommitter::new(n_cols, PUBLIC_STRING);
+    run_soak_with_committer(config, &vector_committer)
+}
+
+/// Runs the soak loop: each iteration commits to a random image via `compute_commitments`, both
+/// in parallel and on a single thread, and cross-checks the result against recomputations of
+/// every row from the precomputed doublings and with the MSM (and periodically against the naive
+/// reference), see `SoakCheck`. Silent arithmetic corruption shows up as a mismatch.
+/// Errors with `HyraxError::InputTooLarge` before the first iteration if `config.data_len`
+/// exceeds `MAX_INPUT_LEN`, and with `HyraxError::ThreadPoolBuild` if the single-thread pool
+/// cannot be built.
+pub fn run_soak_with_committer<C: PrimeOrderCurve>(
+    config: &SoakConfig,
+    vector_committer: &PedersenCommitter<C>,
+) -> Result<SoakReport, HyraxError> {
+    validate_input_len(config.data_len, MAX_INPUT_LEN)?;
+    // --- Built once, rather than per iteration as `compute_commitments_with_max_threads` would ---
+    let single_thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .map_err(|err| HyraxError::ThreadPoolBuild(err.to_string()))?;
+    let mut rng = ChaCha20Rng::seed_from_u64(config.rng_seed);
+    let mut report = SoakReport::default();
+
+    for iteration in 0..config.iterations {
+        let mut data = vec![0_u8; config.data_len];
+        rng.fill_bytes(&mut data);
+        let mut blinding_factor_seed = [0_u8; 32];
+        rng.fill_bytes(&mut blinding_factor_seed);
+
+        let mut failed_checks = vec![];
+
+        // --- Commit in parallel and serially: the outputs must be identical ---
+        let first = compute_commitments(&data, vector_committer, blinding_factor_seed)?;
+        let serial = compute_commitments_in_pool(
+            &data,
+            vector_committer,
+            blinding_factor_seed,
+            &single_thread_pool,
+        )?;
+        if first.commitment != serial.commitment
+            || first.blinding_factors != serial.blinding_factors
+        {
+            failed_checks.push(SoakCheck::Serial);
+        }
+
+        // --- Recompute every row from the precomputed doublings, whatever the row length ---
+        let doublings_commitment = pad_to_power_of_two(&data)
+            .chunks(vector_committer.generators.len())
+            .zip(first.blinding_factors.iter())
+            .map(|(chunk, blind)| vector_committer.vector_commit_with_doublings(chunk, blind))
+            .collect_vec();
+        if doublings_commitment != first.commitment {
+            failed_checks.push(SoakCheck::Doublings);
+        }
+
+        // --- Recompute every row with the MSM ---
+        let msm_commitment = recompute_rows(&data, vector_committer, &first, |scalars, gens| {
+            C::msm(scalars, gens)
+        });
+        if msm_commitment != first.commitment {
+            failed_checks.push(SoakCheck::Msm);
+        }
+
+        // --- Periodically recompute every row with naive scalar multiplications ---
+        if config.reference_check_interval > 0 && iteration % config.reference_check_interval == 0 {
+            report.reference_checks += 1;
+            let reference_commitment =
+                recompute_rows(&data, vector_committer, &first, |scalars, gens| {
+                    scalars
+                        .iter()
+                        .zip(gens.iter())
+                        .fold(C::zero(), |acc, (scalar, generator)| {
+                            acc + *generator * *scalar
+                        })
+                });
+            if reference_commitment != first.commitment {
+                failed_checks.push(SoakCheck::Reference);
+            }
+        }
+
+        if !failed_checks.is_empty() {
+            report.mismatches.push(SoakMismatch {
+                iteration,
+                failed_checks,
+                data,
+                blinding_factor_seed,
+            });
+        }
+        report.iterations_completed += 1;
+    }
+
+    Ok(report)
+}
+
+// Recomputes the row commitments of `output` with the given unblinded-commitment function,
+// reusing the blinding factors from `output`.
+fn recompute_rows<C: PrimeOrderCurve>(
+    data: &[u8],
+    vector_committer: &PedersenCommitter<C>,
+    output: &HyraxCommitmentOutput<C>,
+    unblinded_commit: impl Fn(&[C::Scalar], &[C]) -> C,
+) -> Vec<C> {
+    let data_vec = pad_to_power_of_two(data);
+    let n_cols = vector_committer.generators.len();
+    data_vec
+        .chunks(n_cols)
+        .zip(output.blinding_factors.iter())
+        .map(|(chunk, blind)| {
+            let scalars = chunk
+                .iter()
+                .map(|value| C::Scalar::from(*value as u64))
+                .collect_vec();
+            unblinded_commit(&scalars, &vector_committer.generators[..chunk.len()])
+                + vector_committer.blinding_generator * *blind
+        })
+        .collect()
+}
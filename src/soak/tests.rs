@@ -0,0 +1,88 @@
+use super::*;
+use ark_bn254::G1Projective as Bn256Point;
+
+fn tiny_config() -> SoakConfig {
+    SoakConfig {
+        iterations: 3,
+        data_len: 40,
+        log_num_cols: 3,
+        rng_seed: 1,
+        reference_check_interval: 2,
+    }
+}
+
+#[test]
+fn test_soak_tiny_config_has_no_mismatches() {
+    let report = run_soak::<Bn256Point>(&tiny_config()).unwrap();
+    assert_eq!(report.iterations_completed, 3);
+    // --- iterations 0 and 2 run the reference check ---
+    assert_eq!(report.reference_checks, 2);
+    assert!(report.mismatches.is_empty());
+}
+
+#[test]
+fn test_soak_detects_corrupted_generator() {
+    let mut vector_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << 3, PUBLIC_STRING);
+    // --- Corrupt a generator without touching its precomputed doublings ---
+    vector_committer.generators[1] = vector_committer.generators[1].double();
+
+    let report = run_soak_with_committer(&tiny_config(), &vector_committer).unwrap();
+    assert_eq!(report.mismatches.len(), 3);
+    assert_eq!(
+        report.mismatches[0].failed_checks,
+        vec![SoakCheck::Msm, SoakCheck::Reference]
+    );
+    assert_eq!(report.mismatches[1].failed_checks, vec![SoakCheck::Msm]);
+
+    // --- The report carries the inputs needed to reproduce the mismatch ---
+    let json = serde_json::to_string(&report).unwrap();
+    let parsed: SoakReport = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.mismatches[0].data, report.mismatches[0].data);
+    assert_eq!(parsed.mismatches[0].data.len(), 40);
+}
+
+#[test]
+fn test_soak_checks_doublings_for_msm_rows() {
+    // --- Rows of 128 elements go through the bucket MSM, which reads the generators ---
+    let mut vector_committer: PedersenCommitter<Bn256Point> =
+        PedersenCommitter::new(1 << 7, PUBLIC_STRING);
+    vector_committer.generators[1] = vector_committer.generators[1].double();
+    let config = SoakConfig {
+        iterations: 1,
+        data_len: 256,
+        log_num_cols: 7,
+        ..tiny_config()
+    };
+
+    let report = run_soak_with_committer(&config, &vector_committer).unwrap();
+    assert_eq!(report.mismatches.len(), 1);
+    assert_eq!(
+        report.mismatches[0].failed_checks,
+        vec![SoakCheck::Doublings]
+    );
+}
+
+#[test]
+fn test_soak_bad_config_errors() {
+    use crate::error::HyraxError;
+    use crate::iriscode_commit::MAX_INPUT_LEN;
+
+    let config = SoakConfig {
+        data_len: MAX_INPUT_LEN + 1,
+        ..tiny_config()
+    };
+    assert_eq!(
+        run_soak::<Bn256Point>(&config).map(|report| report.iterations_completed),
+        Err(HyraxError::InputTooLarge {
+            len: MAX_INPUT_LEN + 1,
+            max: MAX_INPUT_LEN
+        })
+    );
+
+    let config = SoakConfig {
+        log_num_cols: usize::BITS as usize,
+        ..tiny_config()
+    };
+    assert!(run_soak::<Bn256Point>(&config).is_err());
+}